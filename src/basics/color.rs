@@ -0,0 +1,104 @@
+use std::ops::{Add, AddAssign, Div, Mul};
+
+/// 线性浮点 RGB 颜色（辐射度），各分量非负且不设上限
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Default)]
+pub struct Color(f64, f64, f64);
+
+impl Color {
+    pub fn new() -> Self {
+        Self(0.0, 0.0, 0.0)
+    }
+
+    pub fn new_from(r: f64, g: f64, b: f64) -> Self {
+        Self(r, g, b)
+    }
+
+    pub fn r(&self) -> f64 {
+        self.0
+    }
+    pub fn g(&self) -> f64 {
+        self.1
+    }
+    pub fn b(&self) -> f64 {
+        self.2
+    }
+
+    /// 将各分量截断到 `[0, 1]`，使其可以写入 `ImgPixel`
+    pub fn clamp(&self) -> Self {
+        Self(
+            self.r().clamp(0.0, 1.0),
+            self.g().clamp(0.0, 1.0),
+            self.b().clamp(0.0, 1.0),
+        )
+    }
+
+    /// 对各分量做 `1 / gamma` 次幂的伽马校正（负分量视为 0.0）
+    pub fn gamma_corrected(&self, gamma: f64) -> Self {
+        let correct = |value: f64| -> f64 { value.max(0.0).powf(1.0 / gamma) };
+        Self(correct(self.r()), correct(self.g()), correct(self.b()))
+    }
+
+    /// 按 Rec. 709 权重计算的亮度
+    pub fn luminance(&self) -> f64 {
+        0.2126 * self.r() + 0.7152 * self.g() + 0.0722 * self.b()
+    }
+
+    /// 最大的分量
+    pub fn max_component(&self) -> f64 {
+        self.r().max(self.g()).max(self.b())
+    }
+
+    pub fn is_black(&self) -> bool {
+        self.r() <= 0.0 && self.g() <= 0.0 && self.b() <= 0.0
+    }
+
+    /// 是否含有 `f64::NAN` 或无穷大分量
+    pub fn is_finite(&self) -> bool {
+        self.r().is_finite() && self.g().is_finite() && self.b().is_finite()
+    }
+}
+
+impl Add<Color> for Color {
+    type Output = Color;
+    fn add(self, rhs: Color) -> Self::Output {
+        Color(self.r() + rhs.r(), self.g() + rhs.g(), self.b() + rhs.b())
+    }
+}
+
+impl AddAssign<Color> for Color {
+    fn add_assign(&mut self, rhs: Color) {
+        *self = *self + rhs;
+    }
+}
+
+/// 逐分量相乘，用于颜色的衰减与混合
+impl Mul<Color> for Color {
+    type Output = Color;
+    fn mul(self, rhs: Color) -> Self::Output {
+        Color(self.r() * rhs.r(), self.g() * rhs.g(), self.b() * rhs.b())
+    }
+}
+
+impl Mul<f64> for Color {
+    type Output = Color;
+    fn mul(self, rhs: f64) -> Self::Output {
+        Color(self.r() * rhs, self.g() * rhs, self.b() * rhs)
+    }
+}
+
+impl Mul<Color> for f64 {
+    type Output = Color;
+    fn mul(self, rhs: Color) -> Self::Output {
+        Color(self * rhs.r(), self * rhs.g(), self * rhs.b())
+    }
+}
+
+impl Div<f64> for Color {
+    type Output = Color;
+    fn div(self, rhs: f64) -> Self::Output {
+        Color(self.r() / rhs, self.g() / rhs, self.b() / rhs)
+    }
+}
+
+pub const BLACK: Color = Color(0.0, 0.0, 0.0);
+pub const WHITE: Color = Color(1.0, 1.0, 1.0);
@@ -1,8 +1,8 @@
-use std::ops::Sub;
+use std::ops::{Add, Sub};
 
 use crate::basics::vec3::Vec3;
 
-#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Default)]
 pub struct Coord3(f64, f64, f64);
 
 impl Coord3 {
@@ -38,7 +38,7 @@ impl Sub<&Coord3> for &Coord3 {
     fn sub(self, rhs: &Coord3) -> Self::Output {
         let vec1: Vec3 = self.into();
         let vec2: Vec3 = rhs.into();
-        &vec1 - &vec2
+        vec1 - vec2
     }
 }
 
@@ -47,7 +47,7 @@ impl Sub<Coord3> for &Coord3 {
     fn sub(self, rhs: Coord3) -> Self::Output {
         let vec1: Vec3 = self.into();
         let vec2: Vec3 = rhs.into();
-        &vec1 - &vec2
+        vec1 - vec2
     }
 }
 
@@ -56,7 +56,7 @@ impl Sub<&Coord3> for Coord3 {
     fn sub(self, rhs: &Coord3) -> Self::Output {
         let vec1: Vec3 = self.into();
         let vec2: Vec3 = rhs.into();
-        &vec1 - &vec2
+        vec1 - vec2
     }
 }
 
@@ -65,7 +65,35 @@ impl Sub<Coord3> for Coord3 {
     fn sub(self, rhs: Coord3) -> Self::Output {
         let vec1: Vec3 = self.into();
         let vec2: Vec3 = rhs.into();
-        &vec1 - &vec2
+        vec1 - vec2
+    }
+}
+
+impl Add<&Vec3> for &Coord3 {
+    type Output = Coord3;
+    fn add(self, rhs: &Vec3) -> Self::Output {
+        Coord3(self.x() + rhs.x(), self.y() + rhs.y(), self.z() + rhs.z())
+    }
+}
+
+impl Add<Vec3> for &Coord3 {
+    type Output = Coord3;
+    fn add(self, rhs: Vec3) -> Self::Output {
+        Coord3(self.x() + rhs.x(), self.y() + rhs.y(), self.z() + rhs.z())
+    }
+}
+
+impl Add<&Vec3> for Coord3 {
+    type Output = Coord3;
+    fn add(self, rhs: &Vec3) -> Self::Output {
+        Coord3(self.x() + rhs.x(), self.y() + rhs.y(), self.z() + rhs.z())
+    }
+}
+
+impl Add<Vec3> for Coord3 {
+    type Output = Coord3;
+    fn add(self, rhs: Vec3) -> Self::Output {
+        Coord3(self.x() + rhs.x(), self.y() + rhs.y(), self.z() + rhs.z())
     }
 }
 
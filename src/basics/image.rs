@@ -3,7 +3,7 @@ use std::{
     cmp::Ordering,
     error::Error,
     fmt::Display,
-    fs::File,
+    fs::{self, File},
     io::{BufWriter, Write},
 };
 
@@ -19,19 +19,22 @@ pub struct ImgPixel {
 
 impl ImgPixel {
     pub fn new_from(float_r: f64, float_g: f64, float_b: f64) -> Result<Self, Box<dyn Error>> {
-        if float_r > 1.0 || float_r < 0.0 {
+        let float_r = nan::check::<MainErr>(float_r, "ImgPixel::new_from")?;
+        let float_g = nan::check::<MainErr>(float_g, "ImgPixel::new_from")?;
+        let float_b = nan::check::<MainErr>(float_b, "ImgPixel::new_from")?;
+        if !(0.0..=1.0).contains(&float_r) {
             return Err(Box::new(ImageErr::InvalidRgbInputErr));
         }
-        if float_g > 1.0 || float_g < 0.0 {
+        if !(0.0..=1.0).contains(&float_g) {
             return Err(Box::new(ImageErr::InvalidRgbInputErr));
         }
-        if float_b > 1.0 || float_b < 0.0 {
+        if !(0.0..=1.0).contains(&float_b) {
             return Err(Box::new(ImageErr::InvalidRgbInputErr));
         }
         Ok(Self {
-            float_r: nan::check::<MainErr>(float_r, "ImgPixel::new_from")?,
-            float_g: nan::check::<MainErr>(float_g, "ImgPixel::new_from")?,
-            float_b: nan::check::<MainErr>(float_b, "ImgPixel::new_from")?,
+            float_r,
+            float_g,
+            float_b,
         })
     }
 
@@ -66,42 +69,48 @@ impl ImgPixel {
         float_g: f64,
         float_b: f64,
     ) -> Result<&mut Self, Box<dyn Error>> {
-        if float_r > 1.0 || float_r < 0.0 {
+        let float_r = nan::check::<MainErr>(float_r, "ImgPixel::set")?;
+        let float_g = nan::check::<MainErr>(float_g, "ImgPixel::set")?;
+        let float_b = nan::check::<MainErr>(float_b, "ImgPixel::set")?;
+        if !(0.0..=1.0).contains(&float_r) {
             return Err(Box::new(ImageErr::InvalidRgbInputErr));
         }
-        if float_g > 1.0 || float_g < 0.0 {
+        if !(0.0..=1.0).contains(&float_g) {
             return Err(Box::new(ImageErr::InvalidRgbInputErr));
         }
-        if float_b > 1.0 || float_b < 0.0 {
+        if !(0.0..=1.0).contains(&float_b) {
             return Err(Box::new(ImageErr::InvalidRgbInputErr));
         }
-        self.float_r = nan::check::<MainErr>(float_r, "ImgPixel::set")?;
-        self.float_g = nan::check::<MainErr>(float_g, "ImgPixel::set")?;
-        self.float_b = nan::check::<MainErr>(float_b, "ImgPixel::set")?;
+        self.float_r = float_r;
+        self.float_g = float_g;
+        self.float_b = float_b;
         Ok(self)
     }
 
     pub fn set_r(&mut self, float_r: f64) -> Result<&mut Self, Box<dyn Error>> {
-        if float_r > 1.0 || float_r < 0.0 {
+        let float_r = nan::check::<MainErr>(float_r, "ImgPixel::set_r")?;
+        if !(0.0..=1.0).contains(&float_r) {
             return Err(Box::new(ImageErr::InvalidRgbInputErr));
         }
-        self.float_r = nan::check::<MainErr>(float_r, "ImgPixel::set_r")?;
+        self.float_r = float_r;
         Ok(self)
     }
 
     pub fn set_g(&mut self, float_g: f64) -> Result<&mut Self, Box<dyn Error>> {
-        if float_g > 1.0 || float_g < 0.0 {
+        let float_g = nan::check::<MainErr>(float_g, "ImgPixel::set_g")?;
+        if !(0.0..=1.0).contains(&float_g) {
             return Err(Box::new(ImageErr::InvalidRgbInputErr));
         }
-        self.float_g = nan::check::<MainErr>(float_g, "ImgPixel::set_g")?;
+        self.float_g = float_g;
         Ok(self)
     }
 
     pub fn set_b(&mut self, float_b: f64) -> Result<&mut Self, Box<dyn Error>> {
-        if float_b > 1.0 || float_b < 0.0 {
+        let float_b = nan::check::<MainErr>(float_b, "ImgPixel::set_b")?;
+        if !(0.0..=1.0).contains(&float_b) {
             return Err(Box::new(ImageErr::InvalidRgbInputErr));
         }
-        self.float_b = nan::check::<MainErr>(float_b, "ImgPixel::set_b")?;
+        self.float_b = float_b;
         Ok(self)
     }
 
@@ -128,15 +137,16 @@ pub struct Img {
 impl Img {
     /// 从指定宽高参数 `w`、`h` 创建一个新的 `Img`
     ///
-    /// 当宽高参数含 `0` 时返回 `ImageErr::InvalidImgParamErr`
+    /// 当宽高参数含 `0` 或像素总数超出 `usize` 范围时返回 `ImageErr::InvalidImgParamErr`
     pub fn new_from(w: usize, h: usize) -> Result<Self, Box<dyn Error>> {
         if w == 0 || h == 0 {
             return Err(Box::new(ImageErr::InvalidImgParamErr));
         }
+        let pixel_num: usize = w.checked_mul(h).ok_or(ImageErr::InvalidImgParamErr)?;
         Ok(Self {
             width: w,
             height: h,
-            pixels: Vec::with_capacity(w * h),
+            pixels: Vec::with_capacity(pixel_num),
         })
     }
 
@@ -189,14 +199,15 @@ impl Img {
     ///
     /// 如果索引对应位置没有 `ImgPixel`，返回 `ImageErr::InvalidPixelIdxErr`
     pub fn index_of(&self, line: usize, col: usize) -> Result<ImgPixel, Box<dyn Error>> {
-        if line <= 0 || col <= 0 {
+        if line == 0 || col == 0 || line > self.get_h() || col > self.get_w() {
             return Err(Box::new(ImageErr::InvalidPixelIdxErr));
         }
-        if line * col > self.pixels.len() {
+        let idx: usize = (line - 1) * self.get_w() + (col - 1);
+        if idx >= self.pixels.len() {
             return Err(Box::new(ImageErr::InvalidPixelIdxErr));
         }
 
-        Ok(self.pixels[line * col - 1])
+        Ok(self.pixels[idx])
     }
 
     /// 返回最后一个 `ImgPixel` 对应的行（1 索引）、列（1索引）和一个 std::cmp::Ordering
@@ -218,6 +229,67 @@ impl Img {
         (line, col, check_res)
     }
 
+    /// 读取 PPM 图像文件（支持 ASCII 的 P3 与二进制的 P6 格式）
+    ///
+    /// 像素值按文件头中的最大值比例缩放到 `[0, 1]`；
+    /// 文件内容不符合 PPM 格式（包括文件头中的宽高与剩余数据量不符）时返回 `ImageErr::InvalidPpmErr`，
+    /// 读取文件失败时返回相应的 IO 错误
+    pub fn load_ppm(path: &str) -> Result<Self, Box<dyn Error>> {
+        let bytes: Vec<u8> = fs::read(path)?;
+        let mut reader = PpmReader { bytes: &bytes, pos: 0 };
+
+        let binary: bool = match reader.token() {
+            Some(b"P3") => false,
+            Some(b"P6") => true,
+            _ => return Err(Box::new(ImageErr::InvalidPpmErr)),
+        };
+        let w: usize = reader.number()?;
+        let h: usize = reader.number()?;
+        let max_value: usize = reader.number()?;
+        if max_value == 0 || max_value > 65535 {
+            return Err(Box::new(ImageErr::InvalidPpmErr));
+        }
+
+        if binary {
+            // 文件头最后的数值之后仅有一个空白字符
+            reader.pos += 1;
+        }
+
+        // 先按每个像素至少占用的字节数核对剩余数据量，避免按损坏的文件头分配过大的内存：
+        // 二进制格式每个分量占 1 或 2 字节，ASCII 格式每个分量至少为一位数字加一个分隔符（最后一个分量之后可以没有）
+        let pixel_bytes: usize = match (binary, max_value > 255) {
+            (true, false) => 3,
+            (true, true) => 6,
+            (false, _) => 6,
+        };
+        let pixel_num: usize = w.checked_mul(h).ok_or(ImageErr::InvalidPpmErr)?;
+        let remaining: usize = bytes.len().saturating_sub(reader.pos);
+        if pixel_num.checked_mul(pixel_bytes).is_none_or(|needed| needed > remaining + 1) {
+            return Err(Box::new(ImageErr::InvalidPpmErr));
+        }
+
+        let mut img = Self::new_from(w, h)?;
+        let scale: f64 = 1.0 / max_value as f64;
+        let mut read_value = || -> Result<f64, Box<dyn Error>> {
+            let value: usize = if binary {
+                reader.binary_value(max_value > 255)?
+            } else {
+                reader.number()?
+            };
+            if value > max_value {
+                return Err(Box::new(ImageErr::InvalidPpmErr));
+            }
+            Ok(value as f64 * scale)
+        };
+        for _ in 0..pixel_num {
+            let r: f64 = read_value()?;
+            let g: f64 = read_value()?;
+            let b: f64 = read_value()?;
+            img.append(r, g, b)?;
+        }
+        Ok(img)
+    }
+
     /// 私有方法，仅用于检查当前 `Img` 是否满足所含 `ImgPixel` 数等于 `width * height`
     ///
     /// 不满足时输出提示信息并返回 `ImageErr::InvalidPixelsErr`
@@ -243,9 +315,14 @@ impl Img {
     ///
     /// 若当前 `Img` 不满足所含 `ImgPixel` 数等于 `width * height`，会返回 `Img::check` 的返回类型
     pub fn produce(&self) -> Result<(), Box<dyn Error>> {
+        self.produce_to(IMAGE_OUTPUT_PATH)
+    }
+
+    /// 同 `Img::produce`，但写入到指定路径 `path`
+    pub fn produce_to(&self, path: &str) -> Result<(), Box<dyn Error>> {
         self.check()?;
 
-        let f = File::create(IMAGE_OUTPUT_PATH)?;
+        let f = File::create(path)?;
         let mut writer = BufWriter::new(f);
         writeln!(writer, "P3\n{} {}\n255\n", self.width, self.height)?;
         for p in self {
@@ -256,6 +333,63 @@ impl Img {
     }
 }
 
+/// 私有类型，按字节读取 PPM 文件内容
+struct PpmReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> PpmReader<'a> {
+    /// 读取下一个以空白字符分隔的记号，跳过 `#` 开头的注释
+    fn token(&mut self) -> Option<&'a [u8]> {
+        loop {
+            while self.pos < self.bytes.len() && self.bytes[self.pos].is_ascii_whitespace() {
+                self.pos += 1;
+            }
+            if self.pos < self.bytes.len() && self.bytes[self.pos] == b'#' {
+                while self.pos < self.bytes.len() && self.bytes[self.pos] != b'\n' {
+                    self.pos += 1;
+                }
+                continue;
+            }
+            break;
+        }
+        let start: usize = self.pos;
+        while self.pos < self.bytes.len() && !self.bytes[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            None
+        } else {
+            Some(&self.bytes[start..self.pos])
+        }
+    }
+
+    /// 读取下一个十进制数
+    fn number(&mut self) -> Result<usize, Box<dyn Error>> {
+        let token: &[u8] = self.token().ok_or(ImageErr::InvalidPpmErr)?;
+        std::str::from_utf8(token)
+            .ok()
+            .and_then(|t| t.parse::<usize>().ok())
+            .ok_or_else(|| Box::new(ImageErr::InvalidPpmErr) as Box<dyn Error>)
+    }
+
+    /// 读取下一个二进制像素分量，`wide` 为真时为 2 字节大端序，否则为 1 字节
+    fn binary_value(&mut self, wide: bool) -> Result<usize, Box<dyn Error>> {
+        let len: usize = if wide { 2 } else { 1 };
+        if self.pos + len > self.bytes.len() {
+            return Err(Box::new(ImageErr::InvalidPpmErr));
+        }
+        let value: usize = if wide {
+            ((self.bytes[self.pos] as usize) << 8) | self.bytes[self.pos + 1] as usize
+        } else {
+            self.bytes[self.pos] as usize
+        };
+        self.pos += len;
+        Ok(value)
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum ImageErr {
     /// 输入无效的 RGB 浮点值
@@ -266,6 +400,8 @@ pub enum ImageErr {
     InvalidPixelsErr,
     /// 访问的索引不在当前 `Img` 所含 `ImgPixel` 的范围
     InvalidPixelIdxErr,
+    /// 读取的文件不是有效的 PPM 图像
+    InvalidPpmErr,
 }
 
 impl Display for ImageErr {
@@ -282,6 +418,7 @@ impl Display for ImageErr {
             ImageErr::InvalidPixelIdxErr => {
                 write!(f, "invalid index of current image pixel(s)")
             }
+            ImageErr::InvalidPpmErr => write!(f, "invalid ppm file content"),
         }
     }
 }
@@ -307,7 +444,7 @@ impl Iterator for ImgPixelIter {
             return None;
         }
         self.index += 1;
-        return Some(self.pixels[self.index - 1]);
+        Some(self.pixels[self.index - 1])
     }
 }
 
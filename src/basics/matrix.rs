@@ -0,0 +1,440 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::Mul;
+
+use crate::basics::coord3::Coord3;
+use crate::basics::vec3::{Vec3, ZERO_VEC3};
+
+/// 4x4 矩阵，按行存储，用于表示三维空间中的仿射变换
+///
+/// 作用于点时按齐次坐标 `(x, y, z, 1)` 计算，作用于方向向量时按 `(x, y, z, 0)` 计算
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct Matrix4([[f64; 4]; 4]);
+
+impl Matrix4 {
+    /// 零矩阵
+    pub fn new() -> Self {
+        Self([[0.0; 4]; 4])
+    }
+
+    /// 由按行排列的元素创建矩阵
+    pub fn new_from(rows: [[f64; 4]; 4]) -> Self {
+        Self(rows)
+    }
+
+    /// 单位矩阵
+    pub fn identity() -> Self {
+        let mut m: Self = Self::new();
+        for i in 0..4 {
+            m.0[i][i] = 1.0;
+        }
+        m
+    }
+
+    /// 平移变换
+    pub fn translate(offset: &Vec3) -> Self {
+        Self([
+            [1.0, 0.0, 0.0, offset.x()],
+            [0.0, 1.0, 0.0, offset.y()],
+            [0.0, 0.0, 1.0, offset.z()],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// 以原点为中心、沿各坐标轴分别缩放 `x`、`y`、`z` 倍的缩放变换
+    pub fn scale(x: f64, y: f64, z: f64) -> Self {
+        Self([
+            [x, 0.0, 0.0, 0.0],
+            [0.0, y, 0.0, 0.0],
+            [0.0, 0.0, z, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// 绕过原点、方向为 `axis` 的轴按右手定则旋转 `degrees`（角度制）的旋转变换
+    ///
+    /// `axis` 为零向量时返回 `MatrixErr::InvalidParamErr`
+    pub fn rotate(axis: &Vec3, degrees: f64) -> Result<Self, Box<dyn Error>> {
+        if *axis == ZERO_VEC3 {
+            return Err(Box::new(MatrixErr::InvalidParamErr));
+        }
+        let a: Vec3 = axis.normalize();
+        let (x, y, z) = (a.x(), a.y(), a.z());
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        let k: f64 = 1.0 - cos;
+        // Rodrigues 旋转公式
+        Ok(Self([
+            [cos + x * x * k, x * y * k - z * sin, x * z * k + y * sin, 0.0],
+            [y * x * k + z * sin, cos + y * y * k, y * z * k - x * sin, 0.0],
+            [z * x * k - y * sin, z * y * k + x * sin, cos + z * z * k, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]))
+    }
+
+    /// 第 `row` 行、第 `col` 列（均为 0 索引）的元素
+    pub fn get(&self, row: usize, col: usize) -> f64 {
+        self.0[row][col]
+    }
+
+    pub fn get_rows(&self) -> &[[f64; 4]; 4] {
+        &self.0
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut m: Self = Self::new();
+        for (i, row) in self.0.iter().enumerate() {
+            for (j, value) in row.iter().enumerate() {
+                m.0[j][i] = *value;
+            }
+        }
+        m
+    }
+
+    /// 逆矩阵，按带部分主元选取的 Gauss-Jordan 消元求得
+    ///
+    /// 矩阵奇异时返回 `MatrixErr::SingularErr`；消元前先把各行缩放为单位长度，
+    /// 主元相对于所在行的长度判断奇异，使结果与矩阵整体的缩放无关
+    pub fn inverse(&self) -> Result<Self, Box<dyn Error>> {
+        // 对 D·A 消元时右侧由 D 开始，最终得到 (D·A)⁻¹·D = A⁻¹
+        let mut a: [[f64; 4]; 4] = self.0;
+        let mut inv: [[f64; 4]; 4] = Self::new().0;
+        for (i, row) in a.iter_mut().enumerate() {
+            let norm: f64 = row.iter().map(|v| v * v).sum::<f64>().sqrt();
+            if norm == 0.0 || !norm.is_finite() {
+                return Err(Box::new(MatrixErr::SingularErr));
+            }
+            row.iter_mut().for_each(|v| *v /= norm);
+            inv[i][i] = 1.0 / norm;
+        }
+
+        for col in 0..4 {
+            let pivot: usize = (col..4)
+                .max_by(|&r1, &r2| a[r1][col].abs().total_cmp(&a[r2][col].abs()))
+                .unwrap_or(col);
+            if a[pivot][col].abs() < SINGULAR_EPSILON {
+                return Err(Box::new(MatrixErr::SingularErr));
+            }
+            a.swap(col, pivot);
+            inv.swap(col, pivot);
+
+            let reciproc_pivot: f64 = 1.0 / a[col][col];
+            for j in 0..4 {
+                a[col][j] *= reciproc_pivot;
+                inv[col][j] *= reciproc_pivot;
+            }
+            for row in 0..4 {
+                if row == col {
+                    continue;
+                }
+                let factor: f64 = a[row][col];
+                for j in 0..4 {
+                    a[row][j] -= factor * a[col][j];
+                    inv[row][j] -= factor * inv[col][j];
+                }
+            }
+        }
+
+        Ok(Self(inv))
+    }
+
+    /// 变换点 `point`（计入平移）
+    pub fn transform_point(&self, point: &Coord3) -> Coord3 {
+        let m = &self.0;
+        let (x, y, z) = (point.x(), point.y(), point.z());
+        let w: f64 = m[3][0] * x + m[3][1] * y + m[3][2] * z + m[3][3];
+        let w: f64 = if w == 0.0 { 1.0 } else { w };
+        Coord3::new_from(
+            (m[0][0] * x + m[0][1] * y + m[0][2] * z + m[0][3]) / w,
+            (m[1][0] * x + m[1][1] * y + m[1][2] * z + m[1][3]) / w,
+            (m[2][0] * x + m[2][1] * y + m[2][2] * z + m[2][3]) / w,
+        )
+    }
+
+    /// 变换方向向量 `vec`（不计平移）
+    pub fn transform_vec(&self, vec: &Vec3) -> Vec3 {
+        self.linear().transform_vec(vec)
+    }
+
+    /// 变换法向量 `normal`，结果为单位向量
+    ///
+    /// 法向量须按线性部分的逆转置矩阵变换才能与变换后的表面保持垂直，
+    /// 需要反复变换时应先用 `normal_matrix` 求出该矩阵再调用 `Matrix3::transform_vec`
+    ///
+    /// 线性部分奇异时返回 `MatrixErr::SingularErr`
+    pub fn transform_normal(&self, normal: &Vec3) -> Result<Vec3, Box<dyn Error>> {
+        Ok(self.normal_matrix()?.transform_vec(normal).normalize())
+    }
+
+    /// 变换法向量所用的矩阵，即线性部分的逆转置矩阵
+    ///
+    /// 线性部分奇异时返回 `MatrixErr::SingularErr`
+    pub fn normal_matrix(&self) -> Result<Matrix3, Box<dyn Error>> {
+        Ok(self.linear().inverse()?.transpose())
+    }
+
+    /// 左上角 3x3 的线性部分（不含平移）
+    pub fn linear(&self) -> Matrix3 {
+        let m = &self.0;
+        Matrix3([
+            [m[0][0], m[0][1], m[0][2]],
+            [m[1][0], m[1][1], m[1][2]],
+            [m[2][0], m[2][1], m[2][2]],
+        ])
+    }
+
+    /// 由线性部分 `linear` 与平移量 `offset` 组成的仿射变换，先做线性变换再平移
+    pub fn from_linear(linear: &Matrix3, offset: &Vec3) -> Self {
+        let m = &linear.0;
+        Self([
+            [m[0][0], m[0][1], m[0][2], offset.x()],
+            [m[1][0], m[1][1], m[1][2], offset.y()],
+            [m[2][0], m[2][1], m[2][2], offset.z()],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// 行列式，按第一行展开为 3x3 余子式计算
+    pub fn determinant(&self) -> f64 {
+        let m = &self.0;
+        (0..4)
+            .map(|col| {
+                let mut minor: Matrix3 = Matrix3::new();
+                for (i, row) in m[1..].iter().enumerate() {
+                    let kept = (0..4).filter(|&j| j != col);
+                    for (k, j) in kept.enumerate() {
+                        minor.0[i][k] = row[j];
+                    }
+                }
+                let sign: f64 = if col % 2 == 0 { 1.0 } else { -1.0 };
+                sign * m[0][col] * minor.determinant()
+            })
+            .sum()
+    }
+}
+
+impl Default for Matrix4 {
+    /// 默认为单位矩阵（恒等变换）
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Mul<Matrix4> for Matrix4 {
+    type Output = Matrix4;
+    /// 矩阵乘法，`a * b` 表示先做变换 `b` 再做变换 `a`
+    fn mul(self, rhs: Matrix4) -> Self::Output {
+        let mut m: Matrix4 = Matrix4::new();
+        for i in 0..4 {
+            for j in 0..4 {
+                m.0[i][j] = (0..4).map(|k| self.0[i][k] * rhs.0[k][j]).sum();
+            }
+        }
+        m
+    }
+}
+
+impl Mul<&Matrix4> for Matrix4 {
+    type Output = Matrix4;
+    fn mul(self, rhs: &Matrix4) -> Self::Output {
+        self * *rhs
+    }
+}
+
+impl Mul<Matrix4> for &Matrix4 {
+    type Output = Matrix4;
+    fn mul(self, rhs: Matrix4) -> Self::Output {
+        *self * rhs
+    }
+}
+
+impl Mul<&Matrix4> for &Matrix4 {
+    type Output = Matrix4;
+    fn mul(self, rhs: &Matrix4) -> Self::Output {
+        *self * *rhs
+    }
+}
+
+/// 3x3 矩阵，按行存储，用于表示三维空间中的线性变换（旋转、缩放、切变等）
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct Matrix3([[f64; 3]; 3]);
+
+impl Matrix3 {
+    /// 零矩阵
+    pub fn new() -> Self {
+        Self([[0.0; 3]; 3])
+    }
+
+    /// 由按行排列的元素创建矩阵
+    pub fn new_from(rows: [[f64; 3]; 3]) -> Self {
+        Self(rows)
+    }
+
+    /// 由三个列向量创建矩阵，常用于由一组正交基构造旋转
+    pub fn from_cols(c0: &Vec3, c1: &Vec3, c2: &Vec3) -> Self {
+        Self([
+            [c0.x(), c1.x(), c2.x()],
+            [c0.y(), c1.y(), c2.y()],
+            [c0.z(), c1.z(), c2.z()],
+        ])
+    }
+
+    /// 单位矩阵
+    pub fn identity() -> Self {
+        let mut m: Self = Self::new();
+        for i in 0..3 {
+            m.0[i][i] = 1.0;
+        }
+        m
+    }
+
+    /// 沿各坐标轴分别缩放 `x`、`y`、`z` 倍的缩放变换
+    pub fn scale(x: f64, y: f64, z: f64) -> Self {
+        Self([[x, 0.0, 0.0], [0.0, y, 0.0], [0.0, 0.0, z]])
+    }
+
+    /// 绕方向为 `axis` 的轴按右手定则旋转 `degrees`（角度制）的旋转变换
+    ///
+    /// `axis` 为零向量时返回 `MatrixErr::InvalidParamErr`
+    pub fn rotate(axis: &Vec3, degrees: f64) -> Result<Self, Box<dyn Error>> {
+        Ok(Matrix4::rotate(axis, degrees)?.linear())
+    }
+
+    /// 第 `row` 行、第 `col` 列（均为 0 索引）的元素
+    pub fn get(&self, row: usize, col: usize) -> f64 {
+        self.0[row][col]
+    }
+
+    pub fn get_rows(&self) -> &[[f64; 3]; 3] {
+        &self.0
+    }
+
+    pub fn transpose(&self) -> Self {
+        let mut m: Self = Self::new();
+        for (i, row) in self.0.iter().enumerate() {
+            for (j, value) in row.iter().enumerate() {
+                m.0[j][i] = *value;
+            }
+        }
+        m
+    }
+
+    pub fn determinant(&self) -> f64 {
+        let m = &self.0;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1]) - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    /// 逆矩阵，按伴随矩阵除以行列式求得
+    ///
+    /// 矩阵奇异时返回 `MatrixErr::SingularErr`；行列式的绝对值不超过各行长度之积（Hadamard 不等式），
+    /// 以两者之比判断奇异，使结果与矩阵整体的缩放无关
+    pub fn inverse(&self) -> Result<Self, Box<dyn Error>> {
+        let det: f64 = self.determinant();
+        let rows: Vec<Vec3> = self.0.iter().map(|r| Vec3::new_from(r[0], r[1], r[2])).collect();
+        let row_norms: f64 = rows.iter().map(|row| row.magnitude()).product();
+        if det.abs() <= SINGULAR_EPSILON * row_norms {
+            return Err(Box::new(MatrixErr::SingularErr));
+        }
+        // 三行两两叉乘得到伴随矩阵的各列
+        let adjugate: Self = Self::from_cols(
+            &rows[1].cross(&rows[2]),
+            &rows[2].cross(&rows[0]),
+            &rows[0].cross(&rows[1]),
+        );
+        let mut inv: Self = adjugate;
+        for row in inv.0.iter_mut() {
+            for value in row.iter_mut() {
+                *value /= det;
+            }
+        }
+        Ok(inv)
+    }
+
+    /// 变换向量 `vec`
+    pub fn transform_vec(&self, vec: &Vec3) -> Vec3 {
+        let m = &self.0;
+        let (x, y, z) = (vec.x(), vec.y(), vec.z());
+        Vec3::new_from(
+            m[0][0] * x + m[0][1] * y + m[0][2] * z,
+            m[1][0] * x + m[1][1] * y + m[1][2] * z,
+            m[2][0] * x + m[2][1] * y + m[2][2] * z,
+        )
+    }
+}
+
+impl Default for Matrix3 {
+    /// 默认为单位矩阵（恒等变换）
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Mul<Matrix3> for Matrix3 {
+    type Output = Matrix3;
+    /// 矩阵乘法，`a * b` 表示先做变换 `b` 再做变换 `a`
+    fn mul(self, rhs: Matrix3) -> Self::Output {
+        let mut m: Matrix3 = Matrix3::new();
+        for i in 0..3 {
+            for j in 0..3 {
+                m.0[i][j] = (0..3).map(|k| self.0[i][k] * rhs.0[k][j]).sum();
+            }
+        }
+        m
+    }
+}
+
+impl Mul<&Matrix3> for Matrix3 {
+    type Output = Matrix3;
+    fn mul(self, rhs: &Matrix3) -> Self::Output {
+        self * *rhs
+    }
+}
+
+impl Mul<Matrix3> for &Matrix3 {
+    type Output = Matrix3;
+    fn mul(self, rhs: Matrix3) -> Self::Output {
+        *self * rhs
+    }
+}
+
+impl Mul<&Matrix3> for &Matrix3 {
+    type Output = Matrix3;
+    fn mul(self, rhs: &Matrix3) -> Self::Output {
+        *self * *rhs
+    }
+}
+
+impl From<Matrix3> for Matrix4 {
+    /// 不含平移的仿射变换
+    fn from(linear: Matrix3) -> Self {
+        Matrix4::from_linear(&linear, &ZERO_VEC3)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum MatrixErr {
+    /// 输入了无效的变换参数
+    InvalidParamErr,
+    /// 矩阵奇异，不可求逆
+    SingularErr,
+}
+
+impl Display for MatrixErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidParamErr => write!(f, "invalid transform parameter"),
+            Self::SingularErr => write!(f, "singular matrix"),
+        }
+    }
+}
+
+impl Error for MatrixErr {}
+
+impl MatrixErr {
+    pub fn handle(&self) {
+        eprintln!("[Matrix Error] {}", self);
+    }
+}
+
+/// 奇异判据的相对阈值：各行归一化后的主元，或行列式与各行长度之积的比值，小于该值时视矩阵为奇异
+const SINGULAR_EPSILON: f64 = 1e-12;
@@ -1,4 +1,9 @@
 pub mod vec3;
 pub mod coord3;
 pub mod tree;
-pub mod image;
\ No newline at end of file
+pub mod image;
+pub mod random;
+pub mod color;
+pub mod matrix;
+pub mod quaternion;
+pub mod polynomial;
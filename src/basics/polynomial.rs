@@ -0,0 +1,169 @@
+use std::f64::consts::PI;
+
+/// 一元二次方程 `a·x² + b·x + c = 0` 的全部实根，按升序排列
+///
+/// `a` 为零时退化为一次方程；重根只返回一次
+pub fn solve_quadratic(a: f64, b: f64, c: f64) -> Vec<f64> {
+    if a == 0.0 {
+        return if b == 0.0 { Vec::new() } else { vec![-c / b] };
+    }
+    let delta: f64 = b * b - 4.0 * a * c;
+    if delta < 0.0 {
+        return Vec::new();
+    }
+    if delta == 0.0 {
+        return vec![-b / (2.0 * a)];
+    }
+    // 避免 b 与 √Δ 相近时两者相减造成的精度损失
+    let q: f64 = -0.5 * (b + b.signum() * delta.sqrt());
+    let (x1, x2) = (q / a, c / q);
+    if x1 < x2 { vec![x1, x2] } else { vec![x2, x1] }
+}
+
+/// 一元三次方程 `a·x³ + b·x² + c·x + d = 0` 的全部实根，按升序排列
+///
+/// `a` 为零时退化为二次方程
+pub fn solve_cubic(a: f64, b: f64, c: f64, d: f64) -> Vec<f64> {
+    if a == 0.0 {
+        return solve_quadratic(b, c, d);
+    }
+    let (b, c, d) = (b / a, c / a, d / a);
+    // 代入 x = y - b/3 化为缺项三次方程 y³ + p·y + q = 0
+    let shift: f64 = b / 3.0;
+    let p: f64 = c - b * b / 3.0;
+    let q: f64 = 2.0 * b * b * b / 27.0 - b * c / 3.0 + d;
+    let discriminant: f64 = (q / 2.0).powi(2) + (p / 3.0).powi(3);
+
+    let mut roots: Vec<f64> = if discriminant >= 0.0 {
+        // 一个实根（Cardano 公式）
+        let sqrt_disc: f64 = discriminant.sqrt();
+        vec![(-q / 2.0 + sqrt_disc).cbrt() + (-q / 2.0 - sqrt_disc).cbrt() - shift]
+    } else {
+        // 三个不等实根（三角函数解法），此时 p < 0
+        let r: f64 = 2.0 * (-p / 3.0).sqrt();
+        let phi: f64 = ((3.0 * q / (2.0 * p)) * (-3.0 / p).sqrt()).clamp(-1.0, 1.0).acos() / 3.0;
+        (0..3)
+            .map(|k| r * (phi - 2.0 * PI * k as f64 / 3.0).cos() - shift)
+            .collect()
+    };
+    roots.sort_by(f64::total_cmp);
+    roots
+}
+
+/// 一元四次方程 `a·x⁴ + b·x³ + c·x² + d·x + e = 0` 的全部实根，按升序排列
+///
+/// 按 Ferrari 方法分解为两个二次方程求解，再以 Newton 迭代修正舍入误差；`a` 为零时退化为三次方程
+pub fn solve_quartic(a: f64, b: f64, c: f64, d: f64, e: f64) -> Vec<f64> {
+    if a == 0.0 {
+        return solve_cubic(b, c, d, e);
+    }
+    let (b, c, d, e) = (b / a, c / a, d / a, e / a);
+    // 代入 x = y - b/4 化为缺项四次方程 y⁴ + p·y² + q·y + r = 0
+    let shift: f64 = b / 4.0;
+    let p: f64 = c - 3.0 * b * b / 8.0;
+    let q: f64 = d - b * c / 2.0 + b * b * b / 8.0;
+    let r: f64 = e - b * d / 4.0 + b * b * c / 16.0 - 3.0 * b.powi(4) / 256.0;
+
+    // 取预解三次方程的最大实根 m，使 (y² + m)² = (2m - p)·y² - q·y + (m² - r) 右端为完全平方
+    let m: f64 = match solve_cubic(8.0, -4.0 * p, -8.0 * r, 4.0 * p * r - q * q).last() {
+        Some(&m) => m,
+        None => return Vec::new(),
+    };
+    let s_squared: f64 = 2.0 * m - p;
+
+    let mut roots: Vec<f64> = if s_squared <= QUARTIC_EPSILON {
+        // q 近似为零，化为关于 y² 的二次方程
+        solve_quadratic(1.0, p, r)
+            .into_iter()
+            .filter(|&z| z >= 0.0)
+            .flat_map(|z| [-z.sqrt(), z.sqrt()])
+            .collect()
+    } else {
+        let s: f64 = s_squared.sqrt();
+        let mut roots: Vec<f64> = solve_quadratic(1.0, -s, m + q / (2.0 * s));
+        roots.extend(solve_quadratic(1.0, s, m - q / (2.0 * s)));
+        roots
+    };
+
+    let eval = |x: f64| -> (f64, f64) {
+        let value: f64 = (((x + b) * x + c) * x + d) * x + e;
+        let derivative: f64 = ((4.0 * x + 3.0 * b) * x + 2.0 * c) * x + d;
+        (value, derivative)
+    };
+    for root in roots.iter_mut() {
+        *root -= shift;
+        for _ in 0..NEWTON_ITERATIONS {
+            let (value, derivative) = eval(*root);
+            if derivative == 0.0 {
+                break;
+            }
+            *root -= value / derivative;
+        }
+    }
+    roots.sort_by(f64::total_cmp);
+    roots
+}
+
+/// 判定 `2m - p` 为零的阈值
+const QUARTIC_EPSILON: f64 = 1e-12;
+/// 四次方程求根后 Newton 迭代修正的次数
+const NEWTON_ITERATIONS: usize = 2;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 私有函数，逐个比较求得的根与期望的根
+    fn assert_roots(roots: Vec<f64>, expected: &[f64]) {
+        assert_eq!(roots.len(), expected.len(), "roots {:?}, expected {:?}", roots, expected);
+        for (root, expected) in roots.iter().zip(expected) {
+            assert!((root - expected).abs() < 1e-9, "roots {:?}, expected {:?}", roots, expected);
+        }
+    }
+
+    #[test]
+    fn quadratic_roots() {
+        assert_roots(solve_quadratic(1.0, -4.0, 3.0), &[1.0, 3.0]);
+        assert_roots(solve_quadratic(-2.0, 0.0, 8.0), &[-2.0, 2.0]);
+        assert_roots(solve_quadratic(1.0, 2.0, 1.0), &[-1.0]);
+        assert_roots(solve_quadratic(1.0, 0.0, 1.0), &[]);
+        assert_roots(solve_quadratic(0.0, 2.0, -1.0), &[0.5]);
+        assert_roots(solve_quadratic(0.0, 0.0, 1.0), &[]);
+        // b 与 √Δ 相近时较小的根仍应精确
+        let roots: Vec<f64> = solve_quadratic(1.0, -1e8, 1.0);
+        assert!((roots[0] - 1e-8).abs() < 1e-20);
+    }
+
+    #[test]
+    fn cubic_roots() {
+        assert_roots(solve_cubic(1.0, -6.0, 11.0, -6.0), &[1.0, 2.0, 3.0]);
+        assert_roots(solve_cubic(2.0, 0.0, 0.0, -16.0), &[2.0]);
+        assert_roots(solve_cubic(1.0, 0.0, 1.0, 0.0), &[0.0]);
+        assert_roots(solve_cubic(0.0, 1.0, -4.0, 3.0), &[1.0, 3.0]);
+    }
+
+    #[test]
+    fn quartic_roots() {
+        assert_roots(solve_quartic(1.0, -10.0, 35.0, -50.0, 24.0), &[1.0, 2.0, 3.0, 4.0]);
+        assert_roots(solve_quartic(3.0, -30.0, 105.0, -150.0, 72.0), &[1.0, 2.0, 3.0, 4.0]);
+        // 缺少奇次项，走关于 y² 的二次方程分支
+        assert_roots(solve_quartic(1.0, 0.0, -5.0, 0.0, 4.0), &[-2.0, -1.0, 1.0, 2.0]);
+        // (x - 0.5)(x + 2)(x² + 1)
+        assert_roots(solve_quartic(1.0, 1.5, 0.0, 1.5, -1.0), &[-2.0, 0.5]);
+        assert_roots(solve_quartic(1.0, 0.0, 0.0, 0.0, 1.0), &[]);
+        assert_roots(solve_quartic(0.0, 1.0, -6.0, 11.0, -6.0), &[1.0, 2.0, 3.0]);
+        // 根的量级相差较大，如光线源点远离圆环面时
+        let expected: [f64; 4] = [0.25, 1.0, 10.0, 400.0];
+        let [r1, r2, r3, r4] = expected;
+        assert_roots(
+            solve_quartic(
+                1.0,
+                -(r1 + r2 + r3 + r4),
+                r1 * r2 + r1 * r3 + r1 * r4 + r2 * r3 + r2 * r4 + r3 * r4,
+                -(r1 * r2 * r3 + r1 * r2 * r4 + r1 * r3 * r4 + r2 * r3 * r4),
+                r1 * r2 * r3 * r4,
+            ),
+            &expected,
+        );
+    }
+}
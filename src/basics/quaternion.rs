@@ -0,0 +1,260 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::Mul;
+
+use crate::basics::coord3::Coord3;
+use crate::basics::matrix::{Matrix3, Matrix4};
+use crate::basics::vec3::{Vec3, ZERO_VEC3};
+
+/// 四元数 `w + xi + yj + zk`，单位四元数用于表示三维旋转
+///
+/// 与旋转矩阵相比便于复合与插值，且多次复合后只需重新归一化即可消除累积误差
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct Quaternion {
+    /// 实部
+    w: f64,
+    /// 虚部 `(x, y, z)`
+    v: Vec3,
+}
+
+impl Quaternion {
+    pub fn new_from(w: f64, x: f64, y: f64, z: f64) -> Self {
+        Self {
+            w,
+            v: Vec3::new_from(x, y, z),
+        }
+    }
+
+    /// 表示不旋转的单位四元数
+    pub fn identity() -> Self {
+        Self { w: 1.0, v: ZERO_VEC3 }
+    }
+
+    /// 绕方向为 `axis` 的轴按右手定则旋转 `degrees`（角度制）的单位四元数
+    ///
+    /// `axis` 为零向量时返回 `QuaternionErr::InvalidAxisErr`
+    pub fn from_axis_angle(axis: &Vec3, degrees: f64) -> Result<Self, Box<dyn Error>> {
+        if *axis == ZERO_VEC3 {
+            return Err(Box::new(QuaternionErr::InvalidAxisErr));
+        }
+        let (sin, cos) = (degrees.to_radians() / 2.0).sin_cos();
+        Ok(Self {
+            w: cos,
+            v: sin * axis.normalize(),
+        })
+    }
+
+    /// 由旋转矩阵 `m` 求对应的单位四元数
+    ///
+    /// `m` 须为正交且行列式为 1 的矩阵，否则结果无意义
+    pub fn from_matrix(m: &Matrix3) -> Self {
+        let trace: f64 = m.get(0, 0) + m.get(1, 1) + m.get(2, 2);
+        // 选取数值最稳定（被开方数最大）的分量先求
+        if trace > 0.0 {
+            let s: f64 = 2.0 * (trace + 1.0).sqrt();
+            Self::new_from(
+                s / 4.0,
+                (m.get(2, 1) - m.get(1, 2)) / s,
+                (m.get(0, 2) - m.get(2, 0)) / s,
+                (m.get(1, 0) - m.get(0, 1)) / s,
+            )
+        } else if m.get(0, 0) > m.get(1, 1) && m.get(0, 0) > m.get(2, 2) {
+            let s: f64 = 2.0 * (1.0 + m.get(0, 0) - m.get(1, 1) - m.get(2, 2)).sqrt();
+            Self::new_from(
+                (m.get(2, 1) - m.get(1, 2)) / s,
+                s / 4.0,
+                (m.get(0, 1) + m.get(1, 0)) / s,
+                (m.get(0, 2) + m.get(2, 0)) / s,
+            )
+        } else if m.get(1, 1) > m.get(2, 2) {
+            let s: f64 = 2.0 * (1.0 + m.get(1, 1) - m.get(0, 0) - m.get(2, 2)).sqrt();
+            Self::new_from(
+                (m.get(0, 2) - m.get(2, 0)) / s,
+                (m.get(0, 1) + m.get(1, 0)) / s,
+                s / 4.0,
+                (m.get(1, 2) + m.get(2, 1)) / s,
+            )
+        } else {
+            let s: f64 = 2.0 * (1.0 + m.get(2, 2) - m.get(0, 0) - m.get(1, 1)).sqrt();
+            Self::new_from(
+                (m.get(1, 0) - m.get(0, 1)) / s,
+                (m.get(0, 2) + m.get(2, 0)) / s,
+                (m.get(1, 2) + m.get(2, 1)) / s,
+                s / 4.0,
+            )
+        }
+    }
+
+    pub fn get_w(&self) -> f64 {
+        self.w
+    }
+
+    /// 虚部 `(x, y, z)`
+    pub fn get_vec(&self) -> &Vec3 {
+        &self.v
+    }
+
+    pub fn dot(&self, rhs: &Self) -> f64 {
+        self.w * rhs.w + self.v.dot(&rhs.v)
+    }
+
+    pub fn magnitude(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    /// 模长为零时返回 `QuaternionErr::ZeroMagnitudeErr`
+    pub fn normalize(&self) -> Result<Self, Box<dyn Error>> {
+        let magnitude: f64 = self.magnitude();
+        if magnitude < ZERO_EPSILON {
+            return Err(Box::new(QuaternionErr::ZeroMagnitudeErr));
+        }
+        Ok(self.scaled(1.0 / magnitude))
+    }
+
+    /// 共轭四元数，对单位四元数即为其逆，表示反向旋转
+    pub fn conjugate(&self) -> Self {
+        Self { w: self.w, v: -self.v }
+    }
+
+    /// 模长为零时返回 `QuaternionErr::ZeroMagnitudeErr`
+    pub fn inverse(&self) -> Result<Self, Box<dyn Error>> {
+        let squared: f64 = self.dot(self);
+        if squared < ZERO_EPSILON * ZERO_EPSILON {
+            return Err(Box::new(QuaternionErr::ZeroMagnitudeErr));
+        }
+        Ok(self.conjugate().scaled(1.0 / squared))
+    }
+
+    /// 球面线性插值，`t` 为 `0.0` 时得到 `self`，为 `1.0` 时得到 `other`
+    ///
+    /// 两者均应为单位四元数，插值沿较短的弧进行
+    pub fn slerp(&self, other: &Self, t: f64) -> Self {
+        let mut cos: f64 = self.dot(other);
+        // q 与 -q 表示同一旋转，取夹角较小者
+        let other: Self = if cos < 0.0 {
+            cos = -cos;
+            other.scaled(-1.0)
+        } else {
+            *other
+        };
+
+        // 夹角很小时退化为线性插值，避免除以接近零的 sin
+        let (k0, k1) = if cos > 1.0 - SLERP_EPSILON {
+            (1.0 - t, t)
+        } else {
+            let theta: f64 = cos.acos();
+            let sin: f64 = theta.sin();
+            (((1.0 - t) * theta).sin() / sin, (t * theta).sin() / sin)
+        };
+        let q: Self = Self {
+            w: k0 * self.w + k1 * other.w,
+            v: k0 * self.v + k1 * other.v,
+        };
+        q.normalize().unwrap_or(q)
+    }
+
+    /// 用单位四元数旋转向量 `vec`
+    pub fn rotate_vec(&self, vec: &Vec3) -> Vec3 {
+        // v' = v + 2w(u × v) + 2u × (u × v)
+        let t: Vec3 = 2.0 * self.v.cross(vec);
+        vec + self.w * t + self.v.cross(&t)
+    }
+
+    /// 用单位四元数绕原点旋转点 `point`
+    pub fn rotate_point(&self, point: &Coord3) -> Coord3 {
+        let rotated: Vec3 = self.rotate_vec(&Vec3::from(point));
+        Coord3::new_from(rotated.x(), rotated.y(), rotated.z())
+    }
+
+    /// 单位四元数对应的旋转矩阵
+    pub fn to_matrix3(&self) -> Matrix3 {
+        let (w, x, y, z) = (self.w, self.v.x(), self.v.y(), self.v.z());
+        Matrix3::new_from([
+            [1.0 - 2.0 * (y * y + z * z), 2.0 * (x * y - w * z), 2.0 * (x * z + w * y)],
+            [2.0 * (x * y + w * z), 1.0 - 2.0 * (x * x + z * z), 2.0 * (y * z - w * x)],
+            [2.0 * (x * z - w * y), 2.0 * (y * z + w * x), 1.0 - 2.0 * (x * x + y * y)],
+        ])
+    }
+
+    /// 单位四元数对应的（不含平移的）仿射变换矩阵
+    pub fn to_matrix4(&self) -> Matrix4 {
+        Matrix4::from(self.to_matrix3())
+    }
+
+    /// 私有方法，四个分量同乘 `k`
+    fn scaled(&self, k: f64) -> Self {
+        Self {
+            w: self.w * k,
+            v: k * self.v,
+        }
+    }
+}
+
+impl Default for Quaternion {
+    /// 默认为不旋转的单位四元数
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Mul<Quaternion> for Quaternion {
+    type Output = Quaternion;
+    /// Hamilton 积，`a * b` 表示先做旋转 `b` 再做旋转 `a`
+    fn mul(self, rhs: Quaternion) -> Self::Output {
+        Quaternion {
+            w: self.w * rhs.w - self.v.dot(&rhs.v),
+            v: self.w * rhs.v + rhs.w * self.v + self.v.cross(&rhs.v),
+        }
+    }
+}
+
+impl Mul<&Quaternion> for Quaternion {
+    type Output = Quaternion;
+    fn mul(self, rhs: &Quaternion) -> Self::Output {
+        self * *rhs
+    }
+}
+
+impl Mul<Quaternion> for &Quaternion {
+    type Output = Quaternion;
+    fn mul(self, rhs: Quaternion) -> Self::Output {
+        *self * rhs
+    }
+}
+
+impl Mul<&Quaternion> for &Quaternion {
+    type Output = Quaternion;
+    fn mul(self, rhs: &Quaternion) -> Self::Output {
+        *self * *rhs
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum QuaternionErr {
+    /// 旋转轴为零向量
+    InvalidAxisErr,
+    /// 四元数模长为零，不可归一化或求逆
+    ZeroMagnitudeErr,
+}
+
+impl Display for QuaternionErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidAxisErr => write!(f, "rotation axis is a zero vector"),
+            Self::ZeroMagnitudeErr => write!(f, "quaternion has zero magnitude"),
+        }
+    }
+}
+
+impl Error for QuaternionErr {}
+
+impl QuaternionErr {
+    pub fn handle(&self) {
+        eprintln!("[Quaternion Error] {}", self);
+    }
+}
+
+/// 模长小于该值时视四元数为零
+const ZERO_EPSILON: f64 = 1e-12;
+/// 两四元数夹角余弦大于 `1 - SLERP_EPSILON` 时改用线性插值
+const SLERP_EPSILON: f64 = 1e-6;
@@ -0,0 +1,73 @@
+use std::cell::Cell;
+
+use super::vec3::Vec3;
+
+thread_local! {
+    /// 每个线程独立的 xorshift64* 随机数状态
+    static RNG_STATE: Cell<u64> = const { Cell::new(DEFAULT_SEED) };
+}
+
+/// 重设当前线程的随机数种子
+///
+/// 种子为 `0` 时改用默认种子（xorshift 的状态不能为 `0`）
+pub fn seed(value: u64) {
+    let value: u64 = if value == 0 { DEFAULT_SEED } else { value };
+    RNG_STATE.with(|state| state.set(value));
+}
+
+/// 返回 `[0, 1)` 内均匀分布的随机 `f64`
+pub fn random_f64() -> f64 {
+    let bits: u64 = RNG_STATE.with(|state| {
+        let mut x: u64 = state.get();
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        state.set(x);
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    });
+    // 取高 53 位构造尾数，保证结果严格小于 1.0
+    (bits >> 11) as f64 * (1.0 / (1_u64 << 53) as f64)
+}
+
+/// 返回 `[min, max)` 内均匀分布的随机 `f64`
+pub fn random_range(min: f64, max: f64) -> f64 {
+    min + (max - min) * random_f64()
+}
+
+/// 返回单位圆盘内均匀分布的随机点 `(x, y)`
+pub fn random_in_unit_disk() -> (f64, f64) {
+    loop {
+        let x: f64 = random_range(-1.0, 1.0);
+        let y: f64 = random_range(-1.0, 1.0);
+        if x * x + y * y < 1.0 {
+            return (x, y);
+        }
+    }
+}
+
+/// 返回单位球面上均匀分布的随机单位向量
+pub fn random_unit_vec3() -> Vec3 {
+    loop {
+        let v: Vec3 = Vec3::new_from(
+            random_range(-1.0, 1.0),
+            random_range(-1.0, 1.0),
+            random_range(-1.0, 1.0),
+        );
+        let len_squared: f64 = v * v;
+        if 1e-160 < len_squared && len_squared <= 1.0 {
+            return v * (1.0 / len_squared.sqrt());
+        }
+    }
+}
+
+/// splitmix64 哈希，将任意整数打散为看似随机的 `u64`
+///
+/// 结果只取决于输入，可用于为每个像素生成固定的扰动
+pub fn hash_u64(value: u64) -> u64 {
+    let mut z: u64 = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+const DEFAULT_SEED: u64 = 0x853C_49E6_748F_EA9B;
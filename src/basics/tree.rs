@@ -1,7 +1,50 @@
+/// 二叉树，每个结点保存一个值 `parent` 及可选的左右子树
 #[derive(Debug, PartialEq, PartialOrd, Clone)]
 pub struct BiTree<T> {
     parent: T,
-    lchild: Box<BiTree<T>>,
-    rchild: Box<BiTree<T>>,
+    lchild: Option<Box<BiTree<T>>>,
+    rchild: Option<Box<BiTree<T>>>,
 }
 
+impl<T> BiTree<T> {
+    /// 创建只含一个结点的树（叶结点）
+    pub fn new_leaf(parent: T) -> Self {
+        Self {
+            parent,
+            lchild: None,
+            rchild: None,
+        }
+    }
+
+    /// 以 `parent` 为根结点值、`lchild` 与 `rchild` 为左右子树创建一棵树
+    pub fn new_from(parent: T, lchild: BiTree<T>, rchild: BiTree<T>) -> Self {
+        Self {
+            parent,
+            lchild: Some(Box::new(lchild)),
+            rchild: Some(Box::new(rchild)),
+        }
+    }
+
+    pub fn get_parent(&self) -> &T {
+        &self.parent
+    }
+
+    pub fn get_lchild(&self) -> Option<&BiTree<T>> {
+        self.lchild.as_deref()
+    }
+
+    pub fn get_rchild(&self) -> Option<&BiTree<T>> {
+        self.rchild.as_deref()
+    }
+
+    pub fn is_leaf(&self) -> bool {
+        self.lchild.is_none() && self.rchild.is_none()
+    }
+
+    /// 树的深度，只含一个结点的树深度为 1
+    pub fn depth(&self) -> usize {
+        let ldepth: usize = self.get_lchild().map_or(0, |t| t.depth());
+        let rdepth: usize = self.get_rchild().map_or(0, |t| t.depth());
+        1 + ldepth.max(rdepth)
+    }
+}
@@ -2,7 +2,7 @@
 pub struct Vec3(f64, f64, f64);
 
 use super::coord3::Coord3;
-use std::ops::{Add, Mul, Sub};
+use std::ops::{Add, Mul, Neg, Sub};
 
 impl Vec3 {
     pub fn new() -> Self {
@@ -39,6 +39,25 @@ impl Vec3 {
         self.x() * rhs.x() + self.y() * rhs.y() + self.z() * rhs.z()
     }
 
+    /// 以单位法向量 `normal` 为镜面法线的反射向量
+    pub fn reflect(&self, normal: &Self) -> Self {
+        self - 2.0 * self.dot(normal) * normal
+    }
+
+    /// 单位向量经折射率之比（入射侧比折射侧）为 `eta_ratio` 的界面后的折射向量
+    ///
+    /// `normal` 为与入射方向相对的单位法向量；发生全反射时返回 `None`
+    pub fn refract(&self, normal: &Self, eta_ratio: f64) -> Option<Self> {
+        let cos_theta: f64 = (-self.dot(normal)).min(1.0);
+        let sin_theta_squared: f64 = 1.0 - cos_theta * cos_theta;
+        if eta_ratio * eta_ratio * sin_theta_squared > 1.0 {
+            return None;
+        }
+        let r_out_perp: Self = eta_ratio * (self + cos_theta * normal);
+        let r_out_parallel: Self = -(1.0 - r_out_perp.dot(&r_out_perp)).abs().sqrt() * normal;
+        Some(r_out_perp + r_out_parallel)
+    }
+
     pub fn cross(&self, rhs: &Self) -> Self {
         Self::new_from(
             self.y() * rhs.z() - self.z() * rhs.y(),
@@ -46,6 +65,18 @@ impl Vec3 {
             self.x() * rhs.y() - self.y() * rhs.x(),
         )
     }
+
+    /// 与本单位向量两两正交的两个单位向量 `(u, v)`，三者构成右手系 `(u, v, self)`
+    pub fn orthonormal_basis(&self) -> (Self, Self) {
+        let helper: Self = if self.x().abs() > 0.9 {
+            Self::new_from(0.0, 1.0, 0.0)
+        } else {
+            Self::new_from(1.0, 0.0, 0.0)
+        };
+        let v: Self = self.cross(&helper).normalize();
+        let u: Self = v.cross(self);
+        (u, v)
+    }
 }
 
 impl Add<Vec3> for &Vec3 {
@@ -160,6 +191,20 @@ impl Mul<Vec3> for f64 {
     }
 }
 
+impl Neg for &Vec3 {
+    type Output = Vec3;
+    fn neg(self) -> Self::Output {
+        Vec3::new_from(-self.x(), -self.y(), -self.z())
+    }
+}
+
+impl Neg for Vec3 {
+    type Output = Vec3;
+    fn neg(self) -> Self::Output {
+        Vec3::new_from(-self.x(), -self.y(), -self.z())
+    }
+}
+
 impl From<&Coord3> for Vec3 {
     fn from(value: &Coord3) -> Self {
         Self::new_from(value.x(), value.y(), value.z())
@@ -2,3 +2,4 @@ pub mod basics;
 pub mod errors;
 pub mod rays;
 pub mod objects;
+pub mod render;
@@ -1,16 +1,44 @@
 use std::error::Error;
 
 use crate::basics::coord3::Coord3;
+use crate::basics::random;
+use crate::basics::vec3::Vec3;
+use crate::objects::{Bounded, Object};
+use crate::objects::emitter::{Emitter, EmitterSample, area_to_solid_angle};
+use crate::objects::texture::OpaqueTexture;
+use crate::rays::hit::HitRecord;
 use crate::{
     errors::{MainErr, nan},
 };
-use crate::rays::ray::{Ray, RayIntersectOpaque};
+use crate::rays::ray::{
+    FORWARD_T_BOUND, Ray, RayIntersectErr, RayIntersectOpaque, RayIntersectTransparent, t_in_bound,
+};
 
+/// 坐标轴
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+impl Axis {
+    /// 坐标 `coord` 在该轴上的分量
+    pub fn of(&self, coord: &Coord3) -> f64 {
+        match self {
+            Axis::X => coord.x(),
+            Axis::Y => coord.y(),
+            Axis::Z => coord.z(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub struct AlignedBox {
     x_axis_bound: (f64, f64),
     y_axis_bound: (f64, f64),
     z_axis_bound: (f64, f64),
+    texture: Option<OpaqueTexture>,
 }
 
 impl AlignedBox {
@@ -28,11 +56,23 @@ impl AlignedBox {
         check(y_axis_bound)?;
         check(z_axis_bound)?;
 
-        return Ok(Self {
+        Ok(Self {
             x_axis_bound,
             y_axis_bound,
             z_axis_bound,
-        });
+            texture: None,
+        })
+    }
+
+    /// 由两个对角点创建轴对齐盒，两点在各坐标轴上的先后顺序任意
+    pub fn from_corners(p1: &Coord3, p2: &Coord3) -> Self {
+        let bound = |v1: f64, v2: f64| -> (f64, f64) { (v1.min(v2), v1.max(v2)) };
+        Self {
+            x_axis_bound: bound(p1.x(), p2.x()),
+            y_axis_bound: bound(p1.y(), p2.y()),
+            z_axis_bound: bound(p1.z(), p2.z()),
+            texture: None,
+        }
     }
 
     pub fn get_x(&self) -> (f64, f64) {
@@ -47,84 +87,249 @@ impl AlignedBox {
         self.z_axis_bound
     }
 
-    pub fn enter_n_exit(&self, ray: &Ray) -> Result<Option<(Coord3, Coord3)>, Box<dyn Error>> {
-        let t1_x = (self.get_x().0 - ray.get_origin().x()) / ray.get_direction().x();
-        let t2_x = (self.get_x().1 - ray.get_origin().x()) / ray.get_direction().x();
-        let t1_y = (self.get_y().0 - ray.get_origin().y()) / ray.get_direction().y();
-        let t2_y = (self.get_y().1 - ray.get_origin().y()) / ray.get_direction().y();
-        let t1_z = (self.get_z().0 - ray.get_origin().z()) / ray.get_direction().z();
-        let t2_z = (self.get_z().1 - ray.get_origin().z()) / ray.get_direction().z();
-
-        let check_if_enter_exit = |t1:f64, t2: f64| -> bool {
-            if t1 > 0.0 && t2 > 0.0 {
-                return true;
-            } else {
-                return false;
-            }
-        };
+    /// 沿坐标轴 `axis` 的上下界
+    pub fn get_bound(&self, axis: Axis) -> (f64, f64) {
+        match axis {
+            Axis::X => self.x_axis_bound,
+            Axis::Y => self.y_axis_bound,
+            Axis::Z => self.z_axis_bound,
+        }
+    }
+
+    /// 各坐标轴下界组成的角点
+    pub fn get_min(&self) -> Coord3 {
+        Coord3::new_from(self.get_x().0, self.get_y().0, self.get_z().0)
+    }
+
+    /// 各坐标轴上界组成的角点
+    pub fn get_max(&self) -> Coord3 {
+        Coord3::new_from(self.get_x().1, self.get_y().1, self.get_z().1)
+    }
+
+    /// 同时包围 `self` 与 `other` 的最小轴对齐盒（不带材质）
+    pub fn merge(&self, other: &AlignedBox) -> AlignedBox {
+        AlignedBox::from_corners(
+            &Coord3::new_from(
+                self.get_x().0.min(other.get_x().0),
+                self.get_y().0.min(other.get_y().0),
+                self.get_z().0.min(other.get_z().0),
+            ),
+            &Coord3::new_from(
+                self.get_x().1.max(other.get_x().1),
+                self.get_y().1.max(other.get_y().1),
+                self.get_z().1.max(other.get_z().1),
+            ),
+        )
+    }
+
+    /// 盒子的表面积
+    pub fn surface_area(&self) -> f64 {
+        let dx: f64 = self.get_x().1 - self.get_x().0;
+        let dy: f64 = self.get_y().1 - self.get_y().0;
+        let dz: f64 = self.get_z().1 - self.get_z().0;
+        2.0 * (dx * dy + dy * dz + dz * dx)
+    }
+
+    /// 盒子的中心
+    pub fn centroid(&self) -> Coord3 {
+        Coord3::new_from(
+            0.5 * (self.get_x().0 + self.get_x().1),
+            0.5 * (self.get_y().0 + self.get_y().1),
+            0.5 * (self.get_z().0 + self.get_z().1),
+        )
+    }
+
+    /// 盒子在各坐标轴上是否都有有限的边界，无限延伸的物体（如平面）的包围盒不满足
+    pub fn is_bounded(&self) -> bool {
+        [self.get_x(), self.get_y(), self.get_z()]
+            .iter()
+            .all(|(min, max)| min.is_finite() && max.is_finite())
+    }
+
+    /// 盒子跨度最大的坐标轴，跨度相同时依次优先 x、y、z
+    pub fn longest_axis(&self) -> Axis {
+        let extent = |bound: (f64, f64)| -> f64 { bound.1 - bound.0 };
+        let (dx, dy, dz) = (extent(self.get_x()), extent(self.get_y()), extent(self.get_z()));
+        if dx >= dy && dx >= dz {
+            Axis::X
+        } else if dy >= dz {
+            Axis::Y
+        } else {
+            Axis::Z
+        }
+    }
+
+    pub fn get_texture(&self) -> Option<&OpaqueTexture> {
+        self.texture.as_ref()
+    }
+
+    pub fn set_texture(&mut self, texture: OpaqueTexture) -> &mut Self {
+        self.texture = Some(texture);
+        self
+    }
+
+    /// 点 `point` 是否位于盒子内部（不含表面）
+    pub fn contains(&self, point: &Coord3) -> bool {
+        let inside = |value: f64, bound: (f64, f64)| -> bool { bound.0 < value && value < bound.1 };
+        inside(point.x(), self.get_x()) && inside(point.y(), self.get_y()) && inside(point.z(), self.get_z())
+    }
+
+    /// 私有方法，返回盒子表面上的点 `point` 所在面朝外的法向量及该面上的纹理坐标
+    ///
+    /// 所在面取 `point` 距离最近的那个面
+    fn face_at(&self, point: &Coord3) -> (Vec3, (f64, f64)) {
+        let ratio = |value: f64, bound: (f64, f64)| -> f64 { (value - bound.0) / (bound.1 - bound.0) };
+        let (x, y, z) = (point.x(), point.y(), point.z());
 
-        let enter_exit_box_x = check_if_enter_exit(t1_x, t2_x);
-        let enter_exit_box_y = check_if_enter_exit(t1_y, t2_y);
-        let enter_exit_box_z = check_if_enter_exit(t1_z, t2_z);
+        let faces = [
+            ((x - self.get_x().0).abs(), Vec3::new_from(-1.0, 0.0, 0.0)),
+            ((x - self.get_x().1).abs(), Vec3::new_from(1.0, 0.0, 0.0)),
+            ((y - self.get_y().0).abs(), Vec3::new_from(0.0, -1.0, 0.0)),
+            ((y - self.get_y().1).abs(), Vec3::new_from(0.0, 1.0, 0.0)),
+            ((z - self.get_z().0).abs(), Vec3::new_from(0.0, 0.0, -1.0)),
+            ((z - self.get_z().1).abs(), Vec3::new_from(0.0, 0.0, 1.0)),
+        ];
+        let (face_idx, _) = faces
+            .iter()
+            .enumerate()
+            .fold((0, f64::INFINITY), |(best_idx, best_dist), (idx, (dist, _))| {
+                if *dist < best_dist { (idx, *dist) } else { (best_idx, best_dist) }
+            });
 
-        let min = |f1: f64, f2: f64| -> f64 {
-            if f1 > f2 {
-                return f2;
-            } else {
-                return f1;
-            }
+        let uv: (f64, f64) = match face_idx / 2 {
+            0 => (ratio(z, self.get_z()), ratio(y, self.get_y())),
+            1 => (ratio(x, self.get_x()), ratio(z, self.get_z())),
+            _ => (ratio(x, self.get_x()), ratio(y, self.get_y())),
         };
-        let max = |f1: f64, f2: f64| -> f64 {
-            if f1 < f2 {
-                return f2;
-            } else {
-                return f1;
-            }
+        (faces[face_idx].1, uv)
+    }
+
+    /// 私有方法，由光线行进时间 `t` 生成交点记录
+    fn hit_record(&self, ray: &Ray, t: f64) -> HitRecord<'_> {
+        let (outward_normal, uv) = self.face_at(&ray.at(t));
+        let mut hit = HitRecord::new_from(ray, t, outward_normal, uv, self.texture.as_ref());
+        hit.set_emitter(self.as_emitter());
+        hit
+    }
+
+    /// 用 slab 法求光线所在直线进入、离开盒子时的行进时间，光线源点后方的时间为负
+    ///
+    /// 直线与盒子不相交时返回 `None`
+    pub fn line_interval(&self, ray: &Ray) -> Option<(f64, f64)> {
+        let axis_t = |bound: (f64, f64), origin: f64, direction: f64| -> (f64, f64) {
+            let t1 = (bound.0 - origin) / direction;
+            let t2 = (bound.1 - origin) / direction;
+            if t1 > t2 { (t2, t1) } else { (t1, t2) }
         };
 
-        if enter_exit_box_x && enter_exit_box_y && enter_exit_box_z {
-            let enter = Coord3::new_from(min(t1_x, t2_x), min(t1_y, t2_y), min(t1_z, t2_z));
-            let exit = Coord3::new_from(max(t1_x, t2_x), max(t1_y, t2_y), max(t1_z, t2_z));
-            Ok(Some((enter, exit)))
-        } else {
-            Ok(None)
+        let (t_near_x, t_far_x) = axis_t(self.get_x(), ray.get_origin().x(), ray.get_direction().x());
+        let (t_near_y, t_far_y) = axis_t(self.get_y(), ray.get_origin().y(), ray.get_direction().y());
+        let (t_near_z, t_far_z) = axis_t(self.get_z(), ray.get_origin().z(), ray.get_direction().z());
+
+        let t_enter = t_near_x.max(t_near_y).max(t_near_z);
+        let t_exit = t_far_x.min(t_far_y).min(t_far_z);
+
+        if t_enter <= t_exit { Some((t_enter, t_exit)) } else { None }
+    }
+
+    /// 私有方法，用 slab 法求光线进入、离开盒子时沿射出方向行进的时间
+    ///
+    /// 光线所在直线与盒子不相交，或盒子整体位于光线后方时返回 `None`
+    fn slab(&self, ray: &Ray) -> Option<(f64, f64)> {
+        self.line_interval(ray).filter(|(_, t_exit)| *t_exit > 0.0)
+    }
+
+    /// 光线在 `t_bound` 区间内是否穿过盒子（包括光线源点位于盒子内部的情况）
+    ///
+    /// 用于包围盒的快速剔除
+    pub fn is_hit_within(&self, ray: &Ray, t_bound: (f64, f64)) -> bool {
+        self.entry_within(ray, t_bound).is_some()
+    }
+
+    /// 光线在 `t_bound` 区间内穿过盒子时，返回光线在区间内进入盒子的时间，否则返回 `None`
+    ///
+    /// 光线源点位于盒子内部（或进入时间早于 `t_bound.0`）时返回 `t_bound.0`，用于按远近排序遍历
+    pub fn entry_within(&self, ray: &Ray, t_bound: (f64, f64)) -> Option<f64> {
+        match self.slab(ray) {
+            Some((t_enter, t_exit)) if t_enter <= t_bound.1 && t_exit >= t_bound.0 => Some(t_enter.max(t_bound.0)),
+            _ => None,
         }
     }
 }
 
 impl RayIntersectOpaque for AlignedBox {
-    fn intersection(&self, ray: &Ray) -> Result<Option<Coord3>, Box<dyn Error>> {
-        let t1_x = (self.get_x().0 - ray.get_origin().x()) / ray.get_direction().x();
-        let t2_x = (self.get_x().1 - ray.get_origin().x()) / ray.get_direction().x();
-        let t1_y = (self.get_y().0 - ray.get_origin().y()) / ray.get_direction().y();
-        let t2_y = (self.get_y().1 - ray.get_origin().y()) / ray.get_direction().y();
-        let t1_z = (self.get_z().0 - ray.get_origin().z()) / ray.get_direction().z();
-        let t2_z = (self.get_z().1 - ray.get_origin().z()) / ray.get_direction().z();
-
-        let check_if_enter_exit = |t1:f64, t2: f64| -> bool {
-            if t1 > 0.0 && t2 > 0.0 {
-                return true;
-            } else {
-                return false;
-            }
-        };
+    fn intersection(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<Option<HitRecord<'_>>, Box<dyn Error>> {
+        match self.slab(ray) {
+            Some((t_enter, _)) if t_enter < 0.0 => Err(Box::new(RayIntersectErr::InnerRayErr)),
+            Some((t_enter, _)) if t_in_bound(t_enter, t_bound) => Ok(Some(self.hit_record(ray, t_enter))),
+            _ => Ok(None),
+        }
+    }
+}
 
-        let enter_exit_box_x = check_if_enter_exit(t1_x, t2_x);
-        let enter_exit_box_y = check_if_enter_exit(t1_y, t2_y);
-        let enter_exit_box_z = check_if_enter_exit(t1_z, t2_z);
+impl RayIntersectTransparent for AlignedBox {
+    fn enter_n_exit(&self, ray: &Ray) -> Result<Option<(HitRecord<'_>, HitRecord<'_>)>, Box<dyn Error>> {
+        Ok(self
+            .slab(ray)
+            .map(|(t_enter, t_exit)| (self.hit_record(ray, t_enter), self.hit_record(ray, t_exit))))
+    }
+}
 
-        let min = |f1: f64, f2: f64| -> f64 {
-            if f1 > f2 {
-                return f2;
-            } else {
-                return f1;
-            }
-        };
+impl Object for AlignedBox {
+    fn as_emitter(&self) -> Option<&dyn Emitter> {
+        match &self.texture {
+            Some(texture) if texture.is_emissive() => Some(self),
+            _ => None,
+        }
+    }
+
+    fn as_transparent(&self) -> Option<&dyn RayIntersectTransparent> {
+        Some(self)
+    }
+}
+
+impl Emitter for AlignedBox {
+    /// 先按面积比例随机选取一个面，再在该面上均匀采样
+    ///
+    /// 参考点位于盒子内部时无法采样
+    fn sample_emitter(&self, origin: &Coord3) -> Option<EmitterSample> {
+        let texture: &OpaqueTexture = self.texture.as_ref()?;
+        if self.contains(origin) {
+            return None;
+        }
+        let (x, y, z) = (self.get_x(), self.get_y(), self.get_z());
+        let (dx, dy, dz) = (x.1 - x.0, y.1 - y.0, z.1 - z.0);
+        let lerp = |bound: (f64, f64)| -> f64 { random::random_range(bound.0, bound.1) };
+        // 任取一侧后，再以等概率选取该轴两端的面
+        let side = |bound: (f64, f64)| -> f64 { if random::random_f64() < 0.5 { bound.0 } else { bound.1 } };
 
-        if enter_exit_box_x && enter_exit_box_y && enter_exit_box_z {
-            Ok(Some(Coord3::new_from(min(t1_x, t2_x), min(t1_y, t2_y), min(t1_z, t2_z))))
+        let pick: f64 = random::random_f64() * (dy * dz + dz * dx + dx * dy);
+        let point: Coord3 = if pick < dy * dz {
+            Coord3::new_from(side(x), lerp(y), lerp(z))
+        } else if pick < dy * dz + dz * dx {
+            Coord3::new_from(lerp(x), side(y), lerp(z))
         } else {
-            Ok(None)
+            Coord3::new_from(lerp(x), lerp(y), side(z))
+        };
+
+        let (normal, _) = self.face_at(&point);
+        let pdf: f64 = area_to_solid_angle(1.0 / self.surface_area(), origin, &point, &normal);
+        if pdf <= 0.0 {
+            return None;
+        }
+        Some(EmitterSample::new_from(point, pdf, texture.emitted()))
+    }
+
+    fn emitter_pdf(&self, origin: &Coord3, direction: &Vec3) -> f64 {
+        match self.intersection(&Ray::new_from(*origin, *direction), FORWARD_T_BOUND) {
+            Ok(Some(hit)) => area_to_solid_angle(1.0 / self.surface_area(), origin, hit.get_point(), hit.get_normal()),
+            _ => 0.0,
         }
     }
 }
+
+impl Bounded for AlignedBox {
+    fn bounding_box(&self) -> AlignedBox {
+        AlignedBox::from_corners(&self.get_min(), &self.get_max())
+    }
+}
@@ -0,0 +1,206 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::basics::coord3::Coord3;
+use crate::basics::tree::BiTree;
+use crate::objects::{Bounded, Object};
+use crate::objects::alignedbox::{AlignedBox, Axis};
+use crate::rays::hit::HitRecord;
+use crate::rays::ray::{Ray, RayIntersectOpaque};
+
+/// BVH 的结点：内部结点只保存包围盒，叶结点同时保存其中的物体
+pub struct BvhNode {
+    bound: AlignedBox,
+    objects: Vec<Box<dyn Object>>,
+}
+
+impl BvhNode {
+    pub fn get_bound(&self) -> &AlignedBox {
+        &self.bound
+    }
+
+    pub fn get_objects(&self) -> &[Box<dyn Object>] {
+        &self.objects
+    }
+}
+
+/// 层次包围盒（Bounding Volume Hierarchy），以 `AlignedBox` 为包围体
+///
+/// 建树时按物体包围盒中心沿最长轴取中位数划分，
+/// 使最近交点与任意交点查询的复杂度降为对数级别
+pub struct Bvh {
+    tree: BiTree<BvhNode>,
+}
+
+impl Bvh {
+    /// 由一组物体建立 BVH
+    ///
+    /// `objects` 为空时返回 `BvhErr::EmptyObjectsErr`
+    pub fn new_from(objects: Vec<Box<dyn Object>>) -> Result<Self, Box<dyn Error>> {
+        if objects.is_empty() {
+            return Err(Box::new(BvhErr::EmptyObjectsErr));
+        }
+        let bounded: Vec<(AlignedBox, Box<dyn Object>)> = objects
+            .into_iter()
+            .map(|object| (object.bounding_box(), object))
+            .collect();
+        Ok(Self {
+            tree: Self::build(bounded),
+        })
+    }
+
+    pub fn get_tree(&self) -> &BiTree<BvhNode> {
+        &self.tree
+    }
+
+    /// 私有方法，递归建树
+    fn build(mut bounded: Vec<(AlignedBox, Box<dyn Object>)>) -> BiTree<BvhNode> {
+        let bound: AlignedBox = bounded
+            .iter()
+            .map(|(b, _)| b.clone())
+            .reduce(|b1, b2| b1.merge(&b2))
+            .unwrap_or_else(|| AlignedBox::from_corners(&Coord3::new(), &Coord3::new()));
+
+        let leaf = |bounded: Vec<(AlignedBox, Box<dyn Object>)>, bound: AlignedBox| -> BiTree<BvhNode> {
+            BiTree::new_leaf(BvhNode {
+                bound,
+                objects: bounded.into_iter().map(|(_, object)| object).collect(),
+            })
+        };
+
+        if bounded.len() <= MAX_LEAF_OBJECTS {
+            return leaf(bounded, bound);
+        }
+
+        // 在物体包围盒中心构成的范围内选取最长轴作为划分轴
+        let centroid_bound: AlignedBox = bounded
+            .iter()
+            .map(|(b, _)| AlignedBox::from_corners(&b.centroid(), &b.centroid()))
+            .reduce(|b1, b2| b1.merge(&b2))
+            .unwrap_or_else(|| bound.clone());
+        let axis: Axis = centroid_bound.longest_axis();
+        let (axis_min, axis_max) = centroid_bound.get_bound(axis);
+        if axis_max - axis_min <= 0.0 {
+            return leaf(bounded, bound);
+        }
+
+        bounded.sort_by(|(b1, _), (b2, _)| axis.of(&b1.centroid()).total_cmp(&axis.of(&b2.centroid())));
+        let rhalf = bounded.split_off(bounded.len() / 2);
+
+        BiTree::new_from(
+            BvhNode {
+                bound,
+                objects: Vec::new(),
+            },
+            Self::build(bounded),
+            Self::build(rhalf),
+        )
+    }
+
+    /// 私有方法，递归求最近交点
+    fn closest<'a>(
+        node: &'a BiTree<BvhNode>,
+        ray: &Ray,
+        t_bound: (f64, f64),
+    ) -> Result<Option<HitRecord<'a>>, Box<dyn Error>> {
+        if !node.get_parent().bound.is_hit_within(ray, t_bound) {
+            return Ok(None);
+        }
+
+        let mut closest: Option<HitRecord<'a>> = None;
+        let mut t_max: f64 = t_bound.1;
+
+        for object in &node.get_parent().objects {
+            if let Some(hit) = object.hit(ray, (t_bound.0, t_max))? {
+                t_max = hit.get_t();
+                closest = Some(hit);
+            }
+        }
+        // 先访问光线较早进入的子结点，其交点收紧区间后，较远的子结点往往可被整体剔除
+        let entry = |child: Option<&'a BiTree<BvhNode>>, t_max: f64| -> Option<(f64, &'a BiTree<BvhNode>)> {
+            let child = child?;
+            Some((child.get_parent().bound.entry_within(ray, (t_bound.0, t_max))?, child))
+        };
+        let mut children = [entry(node.get_lchild(), t_max), entry(node.get_rchild(), t_max)];
+        if let [Some((t_left, _)), Some((t_right, _))] = children
+            && t_right < t_left
+        {
+            children.swap(0, 1);
+        }
+        for (t_enter, child) in children.into_iter().flatten() {
+            if t_enter > t_max {
+                continue;
+            }
+            if let Some(hit) = Self::closest(child, ray, (t_bound.0, t_max))? {
+                t_max = hit.get_t();
+                closest = Some(hit);
+            }
+        }
+
+        Ok(closest)
+    }
+
+    /// 私有方法，递归检查是否存在任意交点
+    fn any(node: &BiTree<BvhNode>, ray: &Ray, t_bound: (f64, f64)) -> Result<bool, Box<dyn Error>> {
+        if !node.get_parent().bound.is_hit_within(ray, t_bound) {
+            return Ok(false);
+        }
+
+        for object in &node.get_parent().objects {
+            if object.any_hit(ray, t_bound)? {
+                return Ok(true);
+            }
+        }
+        for child in [node.get_lchild(), node.get_rchild()].into_iter().flatten() {
+            if Self::any(child, ray, t_bound)? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+impl RayIntersectOpaque for Bvh {
+    fn intersection(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<Option<HitRecord<'_>>, Box<dyn Error>> {
+        Self::closest(&self.tree, ray, t_bound)
+    }
+}
+
+impl Bounded for Bvh {
+    fn bounding_box(&self) -> AlignedBox {
+        self.tree.get_parent().bound.clone()
+    }
+}
+
+impl Object for Bvh {
+    /// 找到第一个交点即返回，不再寻找最近交点
+    fn any_hit(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<bool, Box<dyn Error>> {
+        Self::any(&self.tree, ray, t_bound)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum BvhErr {
+    /// 没有可供建树的物体
+    EmptyObjectsErr,
+}
+
+impl Display for BvhErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EmptyObjectsErr => write!(f, "no object to build bvh from"),
+        }
+    }
+}
+
+impl Error for BvhErr {}
+
+impl BvhErr {
+    pub fn handle(&self) {
+        eprintln!("[Bvh Error] {}", self);
+    }
+}
+
+/// 叶结点最多容纳的物体数
+const MAX_LEAF_OBJECTS: usize = 4;
@@ -0,0 +1,363 @@
+use std::error::Error;
+use std::f64::consts::PI;
+use std::fmt::Display;
+
+use crate::basics::coord3::Coord3;
+use crate::basics::matrix::Matrix3;
+use crate::basics::polynomial::solve_quadratic;
+use crate::basics::random;
+use crate::basics::vec3::{Vec3, ZERO_VEC3};
+use crate::errors::{MainErr, nan};
+use crate::objects::alignedbox::AlignedBox;
+use crate::objects::disk::disk_bounding_box;
+use crate::objects::emitter::{Emitter, EmitterSample, area_to_solid_angle};
+use crate::objects::texture::OpaqueTexture;
+use crate::objects::{Bounded, Object};
+use crate::rays::hit::HitRecord;
+use crate::rays::ray::{FORWARD_T_BOUND, Ray, RayIntersectErr, RayIntersectOpaque, RayIntersectTransparent, t_in_bound};
+
+/// 底面圆心为 `base`、底面半径为 `radius`、顶点为 `apex` 的圆锥
+///
+/// 带底面（`capped`）的圆锥为封闭实心体，可被穿透；不带底面时只有侧面，光线可由内部击中侧面
+#[derive(Debug, PartialEq, Clone)]
+pub struct OpaqueCone {
+    base: Coord3,
+    apex: Coord3,
+    radius: f64,
+    capped: bool,
+    /// 局部坐标系到场景坐标系的旋转，局部坐标系以 `base` 为原点、z 轴由 `base` 指向 `apex`
+    frame: Matrix3,
+    texture: Option<OpaqueTexture>,
+}
+
+/// 圆锥被击中的表面
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum ConeSurface {
+    Side,
+    Base,
+}
+
+impl OpaqueCone {
+    /// 参数含 `f64::NAN` 时返回 `MainErr`，`base` 与 `apex` 重合或半径不为正时返回 `ConeErr::InvalidParamErr`
+    pub fn new_from(base: Coord3, apex: Coord3, radius: f64, capped: bool) -> Result<Self, Box<dyn Error>> {
+        for value in [base.x(), base.y(), base.z(), apex.x(), apex.y(), apex.z(), radius] {
+            nan::check::<MainErr>(value, "OpaqueCone::new_from")?;
+        }
+        let axis: Vec3 = apex - base;
+        if axis.magnitude() <= 0.0 || radius <= 0.0 || !radius.is_finite() {
+            return Err(Box::new(ConeErr::InvalidParamErr));
+        }
+        let axis: Vec3 = axis.normalize();
+        let (u_axis, v_axis) = axis.orthonormal_basis();
+        Ok(Self {
+            base,
+            apex,
+            radius,
+            capped,
+            frame: Matrix3::from_cols(&u_axis, &v_axis, &axis),
+            texture: None,
+        })
+    }
+
+    pub fn get_base(&self) -> &Coord3 {
+        &self.base
+    }
+
+    pub fn get_apex(&self) -> &Coord3 {
+        &self.apex
+    }
+
+    pub fn get_radius(&self) -> f64 {
+        self.radius
+    }
+
+    pub fn is_capped(&self) -> bool {
+        self.capped
+    }
+
+    pub fn get_texture(&self) -> Option<&OpaqueTexture> {
+        self.texture.as_ref()
+    }
+
+    pub fn set_texture(&mut self, texture: OpaqueTexture) -> &mut Self {
+        self.texture = Some(texture);
+        self
+    }
+
+    /// 底面圆心到顶点的距离
+    pub fn height(&self) -> f64 {
+        (self.apex - self.base).magnitude()
+    }
+
+    /// 圆锥的表面积，带底面时计入底面
+    pub fn area(&self) -> f64 {
+        let side: f64 = PI * self.radius * self.radius.hypot(self.height());
+        if self.capped { side + PI * self.radius * self.radius } else { side }
+    }
+
+    /// 私有方法，光线在局部坐标系中的源点与方向
+    fn to_local(&self, ray: &Ray) -> (Vec3, Vec3) {
+        let inverse: Matrix3 = self.frame.transpose();
+        (
+            inverse.transform_vec(&(ray.get_origin() - self.base)),
+            inverse.transform_vec(ray.get_direction()),
+        )
+    }
+
+    /// 私有方法，局部坐标系中高度 `z` 处的截面半径
+    fn radius_at(&self, z: f64) -> f64 {
+        self.radius * (1.0 - z / self.height())
+    }
+
+    /// 私有方法，局部坐标系中的点 `point` 是否位于带底面圆锥的内部（不含表面）
+    fn contains_local(&self, point: &Vec3) -> bool {
+        self.capped
+            && 0.0 < point.z()
+            && point.z() < self.height()
+            && point.x().powi(2) + point.y().powi(2) < self.radius_at(point.z()).powi(2)
+    }
+
+    /// 私有方法，光线所在直线与圆锥表面全部交点的行进时间及所在表面，按行进时间升序排列
+    fn crossings(&self, ray: &Ray) -> Vec<(f64, ConeSurface)> {
+        let (o, d) = self.to_local(ray);
+        let height: f64 = self.height();
+        // 侧面满足 x² + y² = k²·(h - z)²，其中 k 为底面半径与高之比
+        let k_squared: f64 = (self.radius / height).powi(2);
+        let w: f64 = height - o.z();
+        let mut crossings: Vec<(f64, ConeSurface)> = solve_quadratic(
+            d.x().powi(2) + d.y().powi(2) - k_squared * d.z().powi(2),
+            2.0 * (o.x() * d.x() + o.y() * d.y() + k_squared * w * d.z()),
+            o.x().powi(2) + o.y().powi(2) - k_squared * w * w,
+        )
+        .into_iter()
+        // 排除顶点另一侧的对顶圆锥
+        .filter(|t| (0.0..=height).contains(&(o.z() + t * d.z())))
+        .map(|t| (t, ConeSurface::Side))
+        .collect();
+
+        if self.capped && d.z() != 0.0 {
+            let t: f64 = -o.z() / d.z();
+            if (o.x() + t * d.x()).powi(2) + (o.y() + t * d.y()).powi(2) <= self.radius.powi(2) {
+                crossings.push((t, ConeSurface::Base));
+            }
+        }
+        crossings.sort_by(|(t1, _), (t2, _)| t1.total_cmp(t2));
+        crossings
+    }
+
+    /// 私有方法，由光线行进时间 `t` 与所在表面生成交点记录
+    ///
+    /// 侧面的纹理坐标 `u` 对应绕轴的方位角，`v` 对应沿轴的高度比例；
+    /// 底面的纹理坐标 `u` 同为方位角，`v` 对应到圆心的距离与半径之比
+    fn hit_record(&self, ray: &Ray, t: f64, surface: ConeSurface) -> HitRecord<'_> {
+        let (o, d) = self.to_local(ray);
+        let p: Vec3 = o + t * d;
+        let height: f64 = self.height();
+        let u: f64 = p.y().atan2(p.x()).rem_euclid(2.0 * PI) / (2.0 * PI);
+        let (local_normal, uv) = match surface {
+            ConeSurface::Side => {
+                // 隐式方程 x² + y² - k²·(h - z)² = 0 的梯度，在顶点处退化为零向量时取轴向
+                let k_squared: f64 = (self.radius / height).powi(2);
+                let gradient: Vec3 = Vec3::new_from(p.x(), p.y(), k_squared * (height - p.z()));
+                let normal: Vec3 = if gradient == ZERO_VEC3 { Vec3::new_from(0.0, 0.0, 1.0) } else { gradient };
+                (normal, (u, p.z() / height))
+            }
+            ConeSurface::Base => {
+                let rho: f64 = (p.x().powi(2) + p.y().powi(2)).sqrt();
+                (Vec3::new_from(0.0, 0.0, -1.0), (u, rho / self.radius))
+            }
+        };
+        let outward_normal: Vec3 = self.frame.transform_vec(&local_normal).normalize();
+        let mut hit = HitRecord::new_from(ray, t, outward_normal, uv, self.texture.as_ref());
+        hit.set_emitter(self.as_emitter());
+        hit
+    }
+}
+
+impl RayIntersectOpaque for OpaqueCone {
+    fn intersection(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<Option<HitRecord<'_>>, Box<dyn Error>> {
+        if self.contains_local(&self.to_local(ray).0) {
+            return Err(Box::new(RayIntersectErr::InnerRayErr));
+        }
+        Ok(self
+            .crossings(ray)
+            .into_iter()
+            .find(|(t, _)| t_in_bound(*t, t_bound))
+            .map(|(t, surface)| self.hit_record(ray, t, surface)))
+    }
+}
+
+impl RayIntersectTransparent for OpaqueCone {
+    /// 不带底面的圆锥不是封闭实心体，返回 `None`
+    fn enter_n_exit(&self, ray: &Ray) -> Result<Option<(HitRecord<'_>, HitRecord<'_>)>, Box<dyn Error>> {
+        if !self.capped {
+            return Ok(None);
+        }
+        let crossings: Vec<(f64, ConeSurface)> = self.crossings(ray);
+        match (crossings.first(), crossings.last()) {
+            (Some(&(t_enter, enter)), Some(&(t_exit, exit))) if crossings.len() >= 2 && t_exit > 0.0 => Ok(Some((
+                self.hit_record(ray, t_enter, enter),
+                self.hit_record(ray, t_exit, exit),
+            ))),
+            _ => Ok(None),
+        }
+    }
+}
+
+impl Object for OpaqueCone {
+    fn as_emitter(&self) -> Option<&dyn Emitter> {
+        match &self.texture {
+            Some(texture) if texture.is_emissive() => Some(self),
+            _ => None,
+        }
+    }
+
+    fn as_transparent(&self) -> Option<&dyn RayIntersectTransparent> {
+        if self.capped { Some(self) } else { None }
+    }
+}
+
+impl Emitter for OpaqueCone {
+    /// 先按面积比例选取侧面或底面，再在其上均匀采样
+    ///
+    /// 侧面上高度 `z` 处的截面周长正比于 `h - z`，故取 `z = h·(1 - √ξ)`；参考点位于带底面圆锥的内部时无法采样
+    fn sample_emitter(&self, origin: &Coord3) -> Option<EmitterSample> {
+        let texture: &OpaqueTexture = self.texture.as_ref()?;
+        let inverse: Matrix3 = self.frame.transpose();
+        if self.contains_local(&inverse.transform_vec(&(origin - self.base))) {
+            return None;
+        }
+        let height: f64 = self.height();
+        let phi: f64 = 2.0 * PI * random::random_f64();
+        let (cos_phi, sin_phi) = (phi.cos(), phi.sin());
+
+        let side_area: f64 = PI * self.radius * self.radius.hypot(height);
+        let (local_point, local_normal) = if random::random_f64() * self.area() < side_area {
+            let z: f64 = height * (1.0 - random::random_f64().sqrt());
+            let rho: f64 = self.radius_at(z);
+            (
+                Vec3::new_from(rho * cos_phi, rho * sin_phi, z),
+                Vec3::new_from(cos_phi, sin_phi, self.radius / height),
+            )
+        } else {
+            let rho: f64 = self.radius * random::random_f64().sqrt();
+            (
+                Vec3::new_from(rho * cos_phi, rho * sin_phi, 0.0),
+                Vec3::new_from(0.0, 0.0, -1.0),
+            )
+        };
+        let point: Coord3 = self.base + self.frame.transform_vec(&local_point);
+        let normal: Vec3 = self.frame.transform_vec(&local_normal).normalize();
+
+        let pdf: f64 = area_to_solid_angle(1.0 / self.area(), origin, &point, &normal);
+        if pdf <= 0.0 {
+            return None;
+        }
+        Some(EmitterSample::new_from(point, pdf, texture.emitted()))
+    }
+
+    fn emitter_pdf(&self, origin: &Coord3, direction: &Vec3) -> f64 {
+        match self.intersection(&Ray::new_from(*origin, *direction), FORWARD_T_BOUND) {
+            Ok(Some(hit)) => area_to_solid_angle(1.0 / self.area(), origin, hit.get_point(), hit.get_normal()),
+            _ => 0.0,
+        }
+    }
+}
+
+impl Bounded for OpaqueCone {
+    /// 底面圆盘与顶点包围盒的并集
+    fn bounding_box(&self) -> AlignedBox {
+        let axis: Vec3 = (self.apex - self.base).normalize();
+        disk_bounding_box(&self.base, &axis, self.radius).merge(&AlignedBox::from_corners(&self.apex, &self.apex))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ConeErr {
+    /// 输入了无效的初始化参数
+    InvalidParamErr,
+}
+
+impl Display for ConeErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidParamErr => write!(f, "invalid cone construction"),
+        }
+    }
+}
+
+impl Error for ConeErr {}
+
+impl ConeErr {
+    pub fn handle(&self) {
+        eprintln!("[Cone Error] {}", self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 私有函数，底面圆心为原点、顶点为 `(0, 0, 2)`、底面半径为 1 的圆锥
+    fn cone(capped: bool) -> OpaqueCone {
+        OpaqueCone::new_from(Coord3::new_from(0.0, 0.0, 0.0), Coord3::new_from(0.0, 0.0, 2.0), 1.0, capped).unwrap()
+    }
+
+    fn vertical(x: f64, z: f64, dz: f64) -> Ray {
+        Ray::new_from(Coord3::new_from(x, 0.0, z), Vec3::new_from(0.0, 0.0, dz))
+    }
+
+    fn assert_close(v1: &Vec3, v2: &Vec3) {
+        assert!((v1 - v2).magnitude() < 1e-9, "{:?} != {:?}", v1, v2);
+    }
+
+    #[test]
+    fn base_is_hit_only_when_capped() {
+        let (capped, open) = (cone(true), cone(false));
+        let up: Ray = vertical(0.5, -1.0, 1.0);
+        let hit = capped.intersection(&up, FORWARD_T_BOUND).unwrap().unwrap();
+        assert!((hit.get_t() - 1.0).abs() < 1e-9 && hit.is_front_face());
+        assert_close(hit.get_normal(), &Vec3::new_from(0.0, 0.0, -1.0));
+        assert!((hit.get_uv().1 - 0.5).abs() < 1e-9);
+
+        // 不带底面时由内部击中侧面：截面半径 0.5 处高度为 1
+        let hit = open.intersection(&up, FORWARD_T_BOUND).unwrap().unwrap();
+        assert!((hit.get_t() - 2.0).abs() < 1e-9 && !hit.is_front_face());
+
+        assert!(capped.intersection(&vertical(1.5, -1.0, 1.0), FORWARD_T_BOUND).unwrap().is_none());
+    }
+
+    #[test]
+    fn side_is_hit_below_the_apex() {
+        let capped: OpaqueCone = cone(true);
+        let hit = capped.intersection(&vertical(0.25, 5.0, -1.0), FORWARD_T_BOUND).unwrap().unwrap();
+        assert!((hit.get_t() - 3.5).abs() < 1e-9 && hit.is_front_face());
+        assert_close(hit.get_normal(), &Vec3::new_from(2.0, 0.0, 1.0).normalize());
+
+        // 沿轴线击中顶点时取轴向法向量
+        let hit = capped.intersection(&vertical(0.0, 5.0, -1.0), FORWARD_T_BOUND).unwrap().unwrap();
+        assert!((hit.get_t() - 3.0).abs() < 1e-9);
+        assert_close(hit.get_normal(), &Vec3::new_from(0.0, 0.0, 1.0));
+
+        // 对顶圆锥不属于圆锥
+        assert!(capped.intersection(&vertical(0.5, 5.0, -1.0), (0.0, 2.5)).unwrap().is_none());
+    }
+
+    #[test]
+    fn capped_cone_is_penetrable() {
+        let (capped, open) = (cone(true), cone(false));
+        let down: Ray = vertical(0.25, 5.0, -1.0);
+        let (enter, exit) = capped.enter_n_exit(&down).unwrap().unwrap();
+        assert!((enter.get_t() - 3.5).abs() < 1e-9 && (exit.get_t() - 5.0).abs() < 1e-9);
+        assert!(enter.is_front_face() && !exit.is_front_face());
+        assert!(open.enter_n_exit(&down).unwrap().is_none());
+
+        // 光线由内部发出时，求交报错，`Object::hit` 取离开点
+        let inner: Ray = vertical(0.25, 1.0, -1.0);
+        assert!(capped.intersection(&inner, FORWARD_T_BOUND).is_err());
+        let hit = capped.hit(&inner, FORWARD_T_BOUND).unwrap().unwrap();
+        assert!((hit.get_t() - 1.0).abs() < 1e-9 && !hit.is_front_face());
+
+        assert!(capped.enter_n_exit(&vertical(0.25, -1.0, -1.0)).unwrap().is_none());
+    }
+}
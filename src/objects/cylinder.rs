@@ -0,0 +1,351 @@
+use std::error::Error;
+use std::f64::consts::PI;
+use std::fmt::Display;
+
+use crate::basics::coord3::Coord3;
+use crate::basics::matrix::Matrix3;
+use crate::basics::polynomial::solve_quadratic;
+use crate::basics::random;
+use crate::basics::vec3::Vec3;
+use crate::errors::{MainErr, nan};
+use crate::objects::alignedbox::AlignedBox;
+use crate::objects::disk::disk_bounding_box;
+use crate::objects::emitter::{Emitter, EmitterSample, area_to_solid_angle};
+use crate::objects::texture::OpaqueTexture;
+use crate::objects::{Bounded, Object};
+use crate::rays::hit::HitRecord;
+use crate::rays::ray::{FORWARD_T_BOUND, Ray, RayIntersectErr, RayIntersectOpaque, RayIntersectTransparent, t_in_bound};
+
+/// 两端面圆心分别为 `base`、`top`，半径为 `radius` 的圆柱
+///
+/// 带端盖（`capped`）的圆柱为封闭实心体，可被穿透；不带端盖时只有侧面，光线可由内部击中侧面
+#[derive(Debug, PartialEq, Clone)]
+pub struct OpaqueCylinder {
+    base: Coord3,
+    top: Coord3,
+    radius: f64,
+    capped: bool,
+    /// 局部坐标系到场景坐标系的旋转，局部坐标系以 `base` 为原点、z 轴由 `base` 指向 `top`
+    frame: Matrix3,
+    texture: Option<OpaqueTexture>,
+}
+
+/// 圆柱被击中的表面
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum CylinderSurface {
+    Side,
+    Base,
+    Top,
+}
+
+impl OpaqueCylinder {
+    /// 参数含 `f64::NAN` 时返回 `MainErr`，`base` 与 `top` 重合或半径不为正时返回 `CylinderErr::InvalidParamErr`
+    pub fn new_from(base: Coord3, top: Coord3, radius: f64, capped: bool) -> Result<Self, Box<dyn Error>> {
+        for value in [base.x(), base.y(), base.z(), top.x(), top.y(), top.z(), radius] {
+            nan::check::<MainErr>(value, "OpaqueCylinder::new_from")?;
+        }
+        let axis: Vec3 = top - base;
+        if axis.magnitude() <= 0.0 || radius <= 0.0 || !radius.is_finite() {
+            return Err(Box::new(CylinderErr::InvalidParamErr));
+        }
+        let axis: Vec3 = axis.normalize();
+        let (u_axis, v_axis) = axis.orthonormal_basis();
+        Ok(Self {
+            base,
+            top,
+            radius,
+            capped,
+            frame: Matrix3::from_cols(&u_axis, &v_axis, &axis),
+            texture: None,
+        })
+    }
+
+    pub fn get_base(&self) -> &Coord3 {
+        &self.base
+    }
+
+    pub fn get_top(&self) -> &Coord3 {
+        &self.top
+    }
+
+    pub fn get_radius(&self) -> f64 {
+        self.radius
+    }
+
+    pub fn is_capped(&self) -> bool {
+        self.capped
+    }
+
+    pub fn get_texture(&self) -> Option<&OpaqueTexture> {
+        self.texture.as_ref()
+    }
+
+    pub fn set_texture(&mut self, texture: OpaqueTexture) -> &mut Self {
+        self.texture = Some(texture);
+        self
+    }
+
+    /// 两端面圆心间的距离
+    pub fn height(&self) -> f64 {
+        (self.top - self.base).magnitude()
+    }
+
+    /// 圆柱的表面积，带端盖时计入两个端盖
+    pub fn area(&self) -> f64 {
+        let side: f64 = 2.0 * PI * self.radius * self.height();
+        if self.capped { side + 2.0 * PI * self.radius * self.radius } else { side }
+    }
+
+    /// 私有方法，光线在局部坐标系中的源点与方向
+    fn to_local(&self, ray: &Ray) -> (Vec3, Vec3) {
+        let inverse: Matrix3 = self.frame.transpose();
+        (
+            inverse.transform_vec(&(ray.get_origin() - self.base)),
+            inverse.transform_vec(ray.get_direction()),
+        )
+    }
+
+    /// 私有方法，局部坐标系中的点 `point` 是否位于带端盖圆柱的内部（不含表面）
+    fn contains_local(&self, point: &Vec3) -> bool {
+        self.capped
+            && point.x().powi(2) + point.y().powi(2) < self.radius.powi(2)
+            && 0.0 < point.z()
+            && point.z() < self.height()
+    }
+
+    /// 私有方法，光线所在直线与圆柱表面全部交点的行进时间及所在表面，按行进时间升序排列
+    fn crossings(&self, ray: &Ray) -> Vec<(f64, CylinderSurface)> {
+        let (o, d) = self.to_local(ray);
+        let height: f64 = self.height();
+        let mut crossings: Vec<(f64, CylinderSurface)> = solve_quadratic(
+            d.x().powi(2) + d.y().powi(2),
+            2.0 * (o.x() * d.x() + o.y() * d.y()),
+            o.x().powi(2) + o.y().powi(2) - self.radius.powi(2),
+        )
+        .into_iter()
+        .filter(|t| (0.0..=height).contains(&(o.z() + t * d.z())))
+        .map(|t| (t, CylinderSurface::Side))
+        .collect();
+
+        if self.capped && d.z() != 0.0 {
+            for (z, surface) in [(0.0, CylinderSurface::Base), (height, CylinderSurface::Top)] {
+                let t: f64 = (z - o.z()) / d.z();
+                if (o.x() + t * d.x()).powi(2) + (o.y() + t * d.y()).powi(2) <= self.radius.powi(2) {
+                    crossings.push((t, surface));
+                }
+            }
+        }
+        crossings.sort_by(|(t1, _), (t2, _)| t1.total_cmp(t2));
+        crossings
+    }
+
+    /// 私有方法，由光线行进时间 `t` 与所在表面生成交点记录
+    ///
+    /// 侧面的纹理坐标 `u` 对应绕轴的方位角，`v` 对应沿轴的高度比例；
+    /// 端盖的纹理坐标 `u` 同为方位角，`v` 对应到圆心的距离与半径之比
+    fn hit_record(&self, ray: &Ray, t: f64, surface: CylinderSurface) -> HitRecord<'_> {
+        let (o, d) = self.to_local(ray);
+        let p: Vec3 = o + t * d;
+        let u: f64 = p.y().atan2(p.x()).rem_euclid(2.0 * PI) / (2.0 * PI);
+        let rho: f64 = (p.x().powi(2) + p.y().powi(2)).sqrt();
+        let (local_normal, uv) = match surface {
+            CylinderSurface::Side => (Vec3::new_from(p.x(), p.y(), 0.0), (u, p.z() / self.height())),
+            CylinderSurface::Base => (Vec3::new_from(0.0, 0.0, -1.0), (u, rho / self.radius)),
+            CylinderSurface::Top => (Vec3::new_from(0.0, 0.0, 1.0), (u, rho / self.radius)),
+        };
+        let outward_normal: Vec3 = self.frame.transform_vec(&local_normal).normalize();
+        let mut hit = HitRecord::new_from(ray, t, outward_normal, uv, self.texture.as_ref());
+        hit.set_emitter(self.as_emitter());
+        hit
+    }
+}
+
+impl RayIntersectOpaque for OpaqueCylinder {
+    fn intersection(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<Option<HitRecord<'_>>, Box<dyn Error>> {
+        if self.contains_local(&self.to_local(ray).0) {
+            return Err(Box::new(RayIntersectErr::InnerRayErr));
+        }
+        Ok(self
+            .crossings(ray)
+            .into_iter()
+            .find(|(t, _)| t_in_bound(*t, t_bound))
+            .map(|(t, surface)| self.hit_record(ray, t, surface)))
+    }
+}
+
+impl RayIntersectTransparent for OpaqueCylinder {
+    /// 不带端盖的圆柱不是封闭实心体，返回 `None`
+    fn enter_n_exit(&self, ray: &Ray) -> Result<Option<(HitRecord<'_>, HitRecord<'_>)>, Box<dyn Error>> {
+        if !self.capped {
+            return Ok(None);
+        }
+        let crossings: Vec<(f64, CylinderSurface)> = self.crossings(ray);
+        match (crossings.first(), crossings.last()) {
+            (Some(&(t_enter, enter)), Some(&(t_exit, exit))) if crossings.len() >= 2 && t_exit > 0.0 => Ok(Some((
+                self.hit_record(ray, t_enter, enter),
+                self.hit_record(ray, t_exit, exit),
+            ))),
+            _ => Ok(None),
+        }
+    }
+}
+
+impl Object for OpaqueCylinder {
+    fn as_emitter(&self) -> Option<&dyn Emitter> {
+        match &self.texture {
+            Some(texture) if texture.is_emissive() => Some(self),
+            _ => None,
+        }
+    }
+
+    fn as_transparent(&self) -> Option<&dyn RayIntersectTransparent> {
+        if self.capped { Some(self) } else { None }
+    }
+}
+
+impl Emitter for OpaqueCylinder {
+    /// 先按面积比例选取侧面或端盖，再在其上均匀采样
+    ///
+    /// 参考点位于带端盖圆柱的内部时无法采样
+    fn sample_emitter(&self, origin: &Coord3) -> Option<EmitterSample> {
+        let texture: &OpaqueTexture = self.texture.as_ref()?;
+        let inverse: Matrix3 = self.frame.transpose();
+        if self.contains_local(&inverse.transform_vec(&(origin - self.base))) {
+            return None;
+        }
+        let height: f64 = self.height();
+        let phi: f64 = 2.0 * PI * random::random_f64();
+        let (cos_phi, sin_phi) = (phi.cos(), phi.sin());
+
+        let side_area: f64 = 2.0 * PI * self.radius * height;
+        let (local_point, local_normal) = if random::random_f64() * self.area() < side_area {
+            let z: f64 = height * random::random_f64();
+            (
+                Vec3::new_from(self.radius * cos_phi, self.radius * sin_phi, z),
+                Vec3::new_from(cos_phi, sin_phi, 0.0),
+            )
+        } else {
+            let rho: f64 = self.radius * random::random_f64().sqrt();
+            let (z, normal_z) = if random::random_f64() < 0.5 { (0.0, -1.0) } else { (height, 1.0) };
+            (
+                Vec3::new_from(rho * cos_phi, rho * sin_phi, z),
+                Vec3::new_from(0.0, 0.0, normal_z),
+            )
+        };
+        let point: Coord3 = self.base + self.frame.transform_vec(&local_point);
+        let normal: Vec3 = self.frame.transform_vec(&local_normal);
+
+        let pdf: f64 = area_to_solid_angle(1.0 / self.area(), origin, &point, &normal);
+        if pdf <= 0.0 {
+            return None;
+        }
+        Some(EmitterSample::new_from(point, pdf, texture.emitted()))
+    }
+
+    fn emitter_pdf(&self, origin: &Coord3, direction: &Vec3) -> f64 {
+        match self.intersection(&Ray::new_from(*origin, *direction), FORWARD_T_BOUND) {
+            Ok(Some(hit)) => area_to_solid_angle(1.0 / self.area(), origin, hit.get_point(), hit.get_normal()),
+            _ => 0.0,
+        }
+    }
+}
+
+impl Bounded for OpaqueCylinder {
+    /// 两端面圆盘包围盒的并集
+    fn bounding_box(&self) -> AlignedBox {
+        let axis: Vec3 = (self.top - self.base).normalize();
+        disk_bounding_box(&self.base, &axis, self.radius).merge(&disk_bounding_box(&self.top, &axis, self.radius))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum CylinderErr {
+    /// 输入了无效的初始化参数
+    InvalidParamErr,
+}
+
+impl Display for CylinderErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidParamErr => write!(f, "invalid cylinder construction"),
+        }
+    }
+}
+
+impl Error for CylinderErr {}
+
+impl CylinderErr {
+    pub fn handle(&self) {
+        eprintln!("[Cylinder Error] {}", self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 私有函数，两端面圆心分别为原点与 `(0, 0, 2)`、半径为 1 的圆柱
+    fn cylinder(capped: bool) -> OpaqueCylinder {
+        OpaqueCylinder::new_from(Coord3::new_from(0.0, 0.0, 0.0), Coord3::new_from(0.0, 0.0, 2.0), 1.0, capped).unwrap()
+    }
+
+    fn downward(x: f64, z: f64) -> Ray {
+        Ray::new_from(Coord3::new_from(x, 0.0, z), Vec3::new_from(0.0, 0.0, -1.0))
+    }
+
+    fn assert_close(v1: &Vec3, v2: &Vec3) {
+        assert!((v1 - v2).magnitude() < 1e-9, "{:?} != {:?}", v1, v2);
+    }
+
+    #[test]
+    fn caps_are_hit_along_the_axis() {
+        let (capped, open) = (cylinder(true), cylinder(false));
+        let hit = capped.intersection(&downward(0.5, 5.0), FORWARD_T_BOUND).unwrap().unwrap();
+        assert!((hit.get_t() - 3.0).abs() < 1e-9);
+        assert!(hit.is_front_face());
+        assert_close(hit.get_normal(), &Vec3::new_from(0.0, 0.0, 1.0));
+        assert!((hit.get_uv().1 - 0.5).abs() < 1e-9);
+
+        let up = Ray::new_from(Coord3::new_from(0.0, 0.5, -1.0), Vec3::new_from(0.0, 0.0, 1.0));
+        let hit = capped.intersection(&up, FORWARD_T_BOUND).unwrap().unwrap();
+        assert!((hit.get_t() - 1.0).abs() < 1e-9);
+        assert_close(hit.get_normal(), &Vec3::new_from(0.0, 0.0, -1.0));
+
+        assert!(capped.intersection(&downward(1.5, 5.0), FORWARD_T_BOUND).unwrap().is_none());
+        assert!(open.intersection(&downward(0.5, 5.0), FORWARD_T_BOUND).unwrap().is_none());
+    }
+
+    #[test]
+    fn capped_cylinder_is_penetrable() {
+        let (capped, open) = (cylinder(true), cylinder(false));
+        let (enter, exit) = capped.enter_n_exit(&downward(0.5, 5.0)).unwrap().unwrap();
+        assert!((enter.get_t() - 3.0).abs() < 1e-9 && (exit.get_t() - 5.0).abs() < 1e-9);
+        assert!(enter.is_front_face() && !exit.is_front_face());
+
+        // 由侧面进入、由端盖离开
+        let oblique = Ray::new_from(Coord3::new_from(-2.0, 0.0, 0.0), Vec3::new_from(1.0, 0.0, 1.0).normalize());
+        let (enter, exit) = capped.enter_n_exit(&oblique).unwrap().unwrap();
+        assert!((enter.get_point() - Coord3::new_from(-1.0, 0.0, 1.0)).magnitude() < 1e-9);
+        assert!((exit.get_point() - Coord3::new_from(0.0, 0.0, 2.0)).magnitude() < 1e-9);
+        assert_close(enter.get_normal(), &Vec3::new_from(-1.0, 0.0, 0.0));
+        assert_close(exit.get_normal(), &Vec3::new_from(0.0, 0.0, -1.0));
+        assert!(open.enter_n_exit(&oblique).unwrap().is_none());
+
+        // 光线由内部发出时，求交报错，`Object::hit` 取离开点
+        let inner = downward(0.5, 1.0);
+        assert!(capped.intersection(&inner, FORWARD_T_BOUND).is_err());
+        let hit = capped.hit(&inner, FORWARD_T_BOUND).unwrap().unwrap();
+        assert!((hit.get_t() - 1.0).abs() < 1e-9 && !hit.is_front_face());
+
+        assert!(capped.enter_n_exit(&downward(0.5, -1.0)).unwrap().is_none());
+    }
+
+    #[test]
+    fn open_cylinder_is_hit_from_inside() {
+        let open: OpaqueCylinder = cylinder(false);
+        let sideways = Ray::new_from(Coord3::new_from(0.0, 0.0, 1.0), Vec3::new_from(1.0, 0.0, 0.0));
+        let hit = open.intersection(&sideways, FORWARD_T_BOUND).unwrap().unwrap();
+        assert!((hit.get_t() - 1.0).abs() < 1e-9 && !hit.is_front_face());
+        assert_close(hit.get_normal(), &Vec3::new_from(-1.0, 0.0, 0.0));
+    }
+}
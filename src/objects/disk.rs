@@ -0,0 +1,225 @@
+use std::error::Error;
+use std::f64::consts::PI;
+use std::fmt::Display;
+
+use crate::basics::coord3::Coord3;
+use crate::basics::random;
+use crate::basics::vec3::{Vec3, ZERO_VEC3};
+use crate::errors::{MainErr, nan};
+use crate::objects::alignedbox::AlignedBox;
+use crate::objects::emitter::{Emitter, EmitterSample, area_to_solid_angle};
+use crate::objects::texture::OpaqueTexture;
+use crate::objects::{Bounded, Object};
+use crate::rays::hit::HitRecord;
+use crate::rays::ray::{FORWARD_T_BOUND, Ray, RayIntersectOpaque, t_in_bound};
+
+/// 圆心为 `center`、法向量为 `normal`、半径为 `radius` 的圆盘
+#[derive(Debug, PartialEq, Clone)]
+pub struct OpaqueDisk {
+    center: Coord3,
+    /// 朝向外侧的单位法向量
+    normal: Vec3,
+    radius: f64,
+    texture: Option<OpaqueTexture>,
+}
+
+impl OpaqueDisk {
+    /// 参数含 `f64::NAN` 时返回 `MainErr`，`normal` 为零向量或半径不为正时返回 `DiskErr::InvalidParamErr`
+    pub fn new_from(center: Coord3, normal: Vec3, radius: f64) -> Result<Self, Box<dyn Error>> {
+        for value in [center.x(), center.y(), center.z(), normal.x(), normal.y(), normal.z(), radius] {
+            nan::check::<MainErr>(value, "OpaqueDisk::new_from")?;
+        }
+        if normal == ZERO_VEC3 || radius <= 0.0 || !radius.is_finite() {
+            return Err(Box::new(DiskErr::InvalidParamErr));
+        }
+        Ok(Self {
+            center,
+            normal: normal.normalize(),
+            radius,
+            texture: None,
+        })
+    }
+
+    pub fn get_center(&self) -> &Coord3 {
+        &self.center
+    }
+
+    pub fn get_normal(&self) -> &Vec3 {
+        &self.normal
+    }
+
+    pub fn get_radius(&self) -> f64 {
+        self.radius
+    }
+
+    pub fn get_texture(&self) -> Option<&OpaqueTexture> {
+        self.texture.as_ref()
+    }
+
+    pub fn set_texture(&mut self, texture: OpaqueTexture) -> &mut Self {
+        self.texture = Some(texture);
+        self
+    }
+
+    /// 圆盘的面积
+    pub fn area(&self) -> f64 {
+        PI * self.radius * self.radius
+    }
+}
+
+impl RayIntersectOpaque for OpaqueDisk {
+    /// 纹理坐标取交点的极坐标：`u` 对应方位角，`v` 对应到圆心的距离与半径之比
+    fn intersection(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<Option<HitRecord<'_>>, Box<dyn Error>> {
+        let denom: f64 = ray.get_direction() * self.normal;
+        if denom.abs() < PARALLEL_EPSILON {
+            return Ok(None);
+        }
+        let t: f64 = (self.center - ray.get_origin()) * self.normal / denom;
+        if !t_in_bound(t, t_bound) {
+            return Ok(None);
+        }
+        let offset: Vec3 = ray.at(t) - self.center;
+        let distance: f64 = offset.magnitude();
+        if distance > self.radius {
+            return Ok(None);
+        }
+        let (u_axis, v_axis) = self.normal.orthonormal_basis();
+        let phi: f64 = (offset * v_axis).atan2(offset * u_axis).rem_euclid(2.0 * PI);
+        let uv: (f64, f64) = (phi / (2.0 * PI), distance / self.radius);
+        let mut hit = HitRecord::new_from(ray, t, self.normal, uv, self.texture.as_ref());
+        hit.set_emitter(self.as_emitter());
+        Ok(Some(hit))
+    }
+}
+
+impl Object for OpaqueDisk {
+    fn as_emitter(&self) -> Option<&dyn Emitter> {
+        match &self.texture {
+            Some(texture) if texture.is_emissive() => Some(self),
+            _ => None,
+        }
+    }
+}
+
+impl Emitter for OpaqueDisk {
+    /// 在圆盘上按面积均匀采样
+    fn sample_emitter(&self, origin: &Coord3) -> Option<EmitterSample> {
+        let texture: &OpaqueTexture = self.texture.as_ref()?;
+        let (u_axis, v_axis) = self.normal.orthonormal_basis();
+        let r: f64 = self.radius * random::random_f64().sqrt();
+        let phi: f64 = 2.0 * PI * random::random_f64();
+        let point: Coord3 = self.center + r * phi.cos() * u_axis + r * phi.sin() * v_axis;
+
+        let pdf: f64 = area_to_solid_angle(1.0 / self.area(), origin, &point, &self.normal);
+        if pdf <= 0.0 {
+            return None;
+        }
+        Some(EmitterSample::new_from(point, pdf, texture.emitted()))
+    }
+
+    fn emitter_pdf(&self, origin: &Coord3, direction: &Vec3) -> f64 {
+        match self.intersection(&Ray::new_from(*origin, *direction), FORWARD_T_BOUND) {
+            Ok(Some(hit)) => area_to_solid_angle(1.0 / self.area(), origin, hit.get_point(), &self.normal),
+            _ => 0.0,
+        }
+    }
+}
+
+impl Bounded for OpaqueDisk {
+    fn bounding_box(&self) -> AlignedBox {
+        disk_bounding_box(&self.center, &self.normal, self.radius)
+    }
+}
+
+/// 圆心为 `center`、单位法向量为 `normal`、半径为 `radius` 的圆盘的包围盒
+///
+/// 圆盘在各坐标轴上的半宽为 `radius * √(1 - n²)`，其中 `n` 为法向量在该轴上的分量；
+/// 厚度为零的轴会向两侧略微扩展
+pub fn disk_bounding_box(center: &Coord3, normal: &Vec3, radius: f64) -> AlignedBox {
+    let half = |n: f64| -> f64 { (radius * (1.0 - n * n).max(0.0).sqrt()).max(BOUND_PADDING) };
+    let half_diagonal: Vec3 = Vec3::new_from(half(normal.x()), half(normal.y()), half(normal.z()));
+    AlignedBox::from_corners(&(center + (-half_diagonal)), &(center + half_diagonal))
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum DiskErr {
+    /// 输入了无效的初始化参数
+    InvalidParamErr,
+}
+
+impl Display for DiskErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidParamErr => write!(f, "invalid disk construction"),
+        }
+    }
+}
+
+impl Error for DiskErr {}
+
+impl DiskErr {
+    pub fn handle(&self) {
+        eprintln!("[Disk Error] {}", self);
+    }
+}
+
+/// 光线方向与圆盘法向量的点积绝对值小于该值时视为平行
+const PARALLEL_EPSILON: f64 = 1e-12;
+const BOUND_PADDING: f64 = 1e-6;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 私有函数，圆心为 `(0, 0, 1)`、法向量沿 z 轴、半径为 2 的圆盘
+    fn disk() -> OpaqueDisk {
+        OpaqueDisk::new_from(Coord3::new_from(0.0, 0.0, 1.0), Vec3::new_from(0.0, 0.0, 1.0), 2.0).unwrap()
+    }
+
+    fn downward(x: f64, y: f64) -> Ray {
+        Ray::new_from(Coord3::new_from(x, y, 5.0), Vec3::new_from(0.0, 0.0, -1.0))
+    }
+
+    fn assert_close(v1: &Vec3, v2: &Vec3) {
+        assert!((v1 - v2).magnitude() < 1e-9, "{:?} != {:?}", v1, v2);
+    }
+
+    #[test]
+    fn disk_is_hit_within_radius() {
+        let disk: OpaqueDisk = disk();
+        let hit = disk.intersection(&downward(1.0, 0.0), FORWARD_T_BOUND).unwrap().unwrap();
+        assert!((hit.get_t() - 4.0).abs() < 1e-9 && hit.is_front_face());
+        assert_close(hit.get_normal(), &Vec3::new_from(0.0, 0.0, 1.0));
+        assert!((hit.get_uv().1 - 0.5).abs() < 1e-9);
+
+        let up = Ray::new_from(Coord3::new_from(0.0, -1.5, 0.0), Vec3::new_from(0.0, 0.0, 1.0));
+        let hit = disk.intersection(&up, FORWARD_T_BOUND).unwrap().unwrap();
+        assert!((hit.get_t() - 1.0).abs() < 1e-9 && !hit.is_front_face());
+        assert!((hit.get_uv().1 - 0.75).abs() < 1e-9);
+
+        assert!(disk.intersection(&downward(1.5, 1.5), FORWARD_T_BOUND).unwrap().is_none());
+        let parallel = Ray::new_from(Coord3::new_from(-5.0, 0.0, 1.0), Vec3::new_from(1.0, 0.0, 0.0));
+        assert!(disk.intersection(&parallel, FORWARD_T_BOUND).unwrap().is_none());
+    }
+
+    #[test]
+    fn bounding_box_of_tilted_disk() {
+        let tilted = OpaqueDisk::new_from(Coord3::new(), Vec3::new_from(1.0, 0.0, 1.0), 2.0).unwrap();
+        let bound: AlignedBox = tilted.bounding_box();
+        let half: f64 = 2.0 * 0.5_f64.sqrt();
+        assert!((bound.get_x().1 - half).abs() < 1e-9 && (bound.get_z().0 + half).abs() < 1e-9);
+        assert!((bound.get_y().1 - 2.0).abs() < 1e-9);
+
+        let (z0, z1) = disk().bounding_box().get_z();
+        assert!(z0 < 1.0 && 1.0 < z1 && z1 - z0 < 1e-3);
+    }
+
+    #[test]
+    fn invalid_disk_is_rejected() {
+        let (center, normal) = (Coord3::new(), Vec3::new_from(0.0, 0.0, 1.0));
+        assert!(OpaqueDisk::new_from(center, normal, 0.0).is_err());
+        assert!(OpaqueDisk::new_from(center, normal, f64::INFINITY).is_err());
+        assert!(OpaqueDisk::new_from(center, ZERO_VEC3, 1.0).is_err());
+        assert!((disk().area() - 4.0 * PI).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,72 @@
+use std::fmt::Debug;
+
+use crate::basics::color::Color;
+use crate::basics::coord3::Coord3;
+use crate::basics::vec3::Vec3;
+
+/// 在发光体表面采样得到的点，由 `Emitter::sample_emitter` 生成
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct EmitterSample {
+    /// 发光体表面上的采样点
+    point: Coord3,
+    /// 由参考点看去，采得该点的概率密度（以立体角计）
+    pdf: f64,
+    /// 采样点向参考点发出的辐射度
+    radiance: Color,
+}
+
+impl EmitterSample {
+    pub fn new_from(point: Coord3, pdf: f64, radiance: Color) -> Self {
+        Self { point, pdf, radiance }
+    }
+
+    pub fn get_point(&self) -> &Coord3 {
+        &self.point
+    }
+
+    pub fn get_pdf(&self) -> f64 {
+        self.pdf
+    }
+
+    pub fn get_radiance(&self) -> Color {
+        self.radiance
+    }
+}
+
+/// 可被直接采样的发光体（面光源）
+///
+/// 供积分器在每次弹射时显式地向光源采样（next-event estimation）
+pub trait Emitter: Debug {
+    /// 由参考点 `origin` 向发光体表面采样一点；无法采样（如参考点在发光体内部）时返回 `None`
+    fn sample_emitter(&self, origin: &Coord3) -> Option<EmitterSample>;
+
+    /// 由参考点 `origin` 沿单位向量 `direction` 射出的光线击中发光体时，
+    /// `sample_emitter` 采得该交点的概率密度（以立体角计）；未击中时返回 `0.0`
+    fn emitter_pdf(&self, origin: &Coord3, direction: &Vec3) -> f64;
+}
+
+/// 将发光体表面上以面积计的概率密度 `area_pdf` 换算为由 `origin` 看去以立体角计的概率密度
+///
+/// `normal` 为采样点 `point` 处的单位法向量；视线与表面相切时返回 `0.0`
+pub fn area_to_solid_angle(area_pdf: f64, origin: &Coord3, point: &Coord3, normal: &Vec3) -> f64 {
+    let to_point: Vec3 = point - origin;
+    let distance_squared: f64 = to_point * to_point;
+    let cos_theta: f64 = (to_point.normalize() * normal).abs();
+    if cos_theta < 1e-8 {
+        return 0.0;
+    }
+    area_pdf * distance_squared / cos_theta
+}
+
+/// `area_to_solid_angle` 的逆换算：将由 `origin` 看去以立体角计的概率密度 `solid_angle_pdf`
+/// 换算为发光体表面上以面积计的概率密度
+///
+/// `normal` 为采样点 `point` 处的单位法向量
+pub fn solid_angle_to_area(solid_angle_pdf: f64, origin: &Coord3, point: &Coord3, normal: &Vec3) -> f64 {
+    let to_point: Vec3 = point - origin;
+    let distance_squared: f64 = to_point * to_point;
+    if distance_squared == 0.0 {
+        return 0.0;
+    }
+    solid_angle_pdf * (to_point.normalize() * normal).abs() / distance_squared
+}
@@ -0,0 +1,173 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::basics::color::Color;
+use crate::basics::coord3::Coord3;
+use crate::basics::image::Img;
+use crate::errors::{MainErr, nan};
+use crate::objects::pattern::Texture;
+
+/// 纹理采样时的过滤方式
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub enum TextureFilter {
+    /// 取最近的像素
+    Nearest,
+    /// 对周围四个像素做双线性插值
+    Bilinear,
+}
+
+/// 纹理坐标超出 `[0, 1]` 时的处理方式
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub enum TextureWrap {
+    /// 重复平铺
+    Repeat,
+    /// 取边缘像素
+    Clamp,
+    /// 镜像平铺
+    Mirror,
+}
+
+/// 图像纹理：按交点的纹理坐标在 `Img` 上取色
+///
+/// 纹理坐标 `(0, 0)` 对应图像左下角，`(1, 1)` 对应图像右上角
+#[derive(Debug, Clone)]
+pub struct ImageTexture {
+    img: Img,
+    filter: TextureFilter,
+    wrap: TextureWrap,
+    /// 取色后对各分量做 `gamma` 次幂，将图像中经伽马编码的颜色还原为线性颜色
+    gamma: f64,
+}
+
+impl ImageTexture {
+    /// 由 `Img` 创建图像纹理，默认为双线性过滤、重复平铺、不做伽马还原
+    pub fn new_from(img: Img) -> Self {
+        Self {
+            img,
+            filter: TextureFilter::Bilinear,
+            wrap: TextureWrap::Repeat,
+            gamma: 1.0,
+        }
+    }
+
+    /// 读取 PPM 文件（P3 或 P6）创建图像纹理，错误同 `Img::load_ppm`
+    pub fn load_ppm(path: &str) -> Result<Self, Box<dyn Error>> {
+        Ok(Self::new_from(Img::load_ppm(path)?))
+    }
+
+    pub fn get_img(&self) -> &Img {
+        &self.img
+    }
+
+    pub fn get_filter(&self) -> TextureFilter {
+        self.filter
+    }
+
+    pub fn get_wrap(&self) -> TextureWrap {
+        self.wrap
+    }
+
+    pub fn get_gamma(&self) -> f64 {
+        self.gamma
+    }
+
+    pub fn set_filter(&mut self, filter: TextureFilter) -> &mut Self {
+        self.filter = filter;
+        self
+    }
+
+    pub fn set_wrap(&mut self, wrap: TextureWrap) -> &mut Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// 设置伽马还原参数（如 `2.2`），`1.0` 表示不还原
+    ///
+    /// `gamma` 为 `f64::NAN` 时返回 `MainErr`，不为正时返回 `ImageTextureErr::InvalidGammaErr`
+    pub fn set_gamma(&mut self, gamma: f64) -> Result<&mut Self, Box<dyn Error>> {
+        let gamma: f64 = nan::check::<MainErr>(gamma, "ImageTexture::set_gamma")?;
+        if gamma <= 0.0 {
+            return Err(Box::new(ImageTextureErr::InvalidGammaErr));
+        }
+        self.gamma = gamma;
+        Ok(self)
+    }
+
+    /// 私有方法，按平铺方式将任意整数索引映射到 `[0, len)` 内
+    fn wrap_index(&self, idx: i64, len: usize) -> usize {
+        let len: i64 = len as i64;
+        let wrapped: i64 = match self.wrap {
+            TextureWrap::Repeat => idx.rem_euclid(len),
+            TextureWrap::Clamp => idx.clamp(0, len - 1),
+            TextureWrap::Mirror => {
+                let period: i64 = idx.rem_euclid(2 * len);
+                if period < len { period } else { 2 * len - 1 - period }
+            }
+        };
+        wrapped as usize
+    }
+
+    /// 私有方法，第 `x` 列、第 `y` 行（均为 0 索引，可越界）处按平铺方式取得的颜色
+    fn texel(&self, x: i64, y: i64) -> Color {
+        let col: usize = self.wrap_index(x, self.img.get_w());
+        let line: usize = self.wrap_index(y, self.img.get_h());
+        match self.img.index_of(line + 1, col + 1) {
+            Ok(pixel) => Color::new_from(pixel.get_r(), pixel.get_g(), pixel.get_b()),
+            // 图像像素不足 `width * height` 时，缺失部分按黑色处理
+            Err(_) => Color::new(),
+        }
+    }
+}
+
+impl Texture for ImageTexture {
+    fn value(&self, uv: (f64, f64), _point: &Coord3) -> Color {
+        // 换算为以像素中心为整数坐标的连续坐标
+        let x: f64 = uv.0 * self.img.get_w() as f64 - 0.5;
+        let y: f64 = (1.0 - uv.1) * self.img.get_h() as f64 - 0.5;
+
+        let color: Color = match self.filter {
+            TextureFilter::Nearest => self.texel(x.round() as i64, y.round() as i64),
+            TextureFilter::Bilinear => {
+                let (x0, y0) = (x.floor(), y.floor());
+                let (fx, fy) = (x - x0, y - y0);
+                let (x0, y0) = (x0 as i64, y0 as i64);
+                (1.0 - fx) * (1.0 - fy) * self.texel(x0, y0)
+                    + fx * (1.0 - fy) * self.texel(x0 + 1, y0)
+                    + (1.0 - fx) * fy * self.texel(x0, y0 + 1)
+                    + fx * fy * self.texel(x0 + 1, y0 + 1)
+            }
+        };
+
+        if self.gamma == 1.0 {
+            color
+        } else {
+            Color::new_from(
+                color.r().powf(self.gamma),
+                color.g().powf(self.gamma),
+                color.b().powf(self.gamma),
+            )
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ImageTextureErr {
+    /// 伽马还原参数无效
+    InvalidGammaErr,
+}
+
+impl Display for ImageTextureErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidGammaErr => write!(f, "invalid gamma value"),
+        }
+    }
+}
+
+impl Error for ImageTextureErr {}
+
+impl ImageTextureErr {
+    pub fn handle(&self) {
+        eprintln!("[Image Texture Error] {}", self);
+    }
+}
@@ -0,0 +1,236 @@
+use std::error::Error;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use crate::basics::coord3::Coord3;
+use crate::basics::matrix::{Matrix3, Matrix4};
+use crate::basics::vec3::Vec3;
+use crate::objects::alignedbox::AlignedBox;
+use crate::objects::emitter::{Emitter, EmitterSample, area_to_solid_angle, solid_angle_to_area};
+use crate::objects::{Bounded, Object};
+use crate::rays::hit::HitRecord;
+use crate::rays::ray::{FORWARD_T_BOUND, Ray, RayIntersectOpaque, RayIntersectTransparent};
+
+/// 物体的实例：对共享的物体施加一个仿射变换后放入场景
+///
+/// 同一物体可被多个实例以不同变换放置多次；求交时把光线变换到物体自身的坐标系，
+/// 再把交点与法向量（按逆转置矩阵）变换回场景坐标系
+///
+/// 被实例化的物体可被直接采样（`Object::as_emitter` 返回 `Some`）时，实例同样可被直接采样，
+/// 采样点与概率密度由物体坐标系换算到场景坐标系
+pub struct Instance {
+    object: Arc<dyn Object>,
+    /// 物体坐标系到场景坐标系的变换
+    transform: Matrix4,
+    /// 场景坐标系到物体坐标系的变换
+    inverse: Matrix4,
+    /// 变换法向量所用的逆转置矩阵
+    normal_matrix: Matrix3,
+}
+
+impl Instance {
+    /// 以物体坐标系到场景坐标系的变换 `transform` 创建 `object` 的实例
+    ///
+    /// `transform` 不可逆时返回 `MatrixErr::SingularErr`
+    pub fn new_from(object: Arc<dyn Object>, transform: Matrix4) -> Result<Self, Box<dyn Error>> {
+        let inverse: Matrix4 = transform.inverse()?;
+        Ok(Self {
+            object,
+            transform,
+            inverse,
+            normal_matrix: transform.normal_matrix()?,
+        })
+    }
+
+    pub fn get_object(&self) -> &Arc<dyn Object> {
+        &self.object
+    }
+
+    pub fn get_transform(&self) -> &Matrix4 {
+        &self.transform
+    }
+
+    pub fn get_inverse(&self) -> &Matrix4 {
+        &self.inverse
+    }
+
+    /// 私有方法，场景坐标系中的光线 `ray` 在物体坐标系中的光线，以及两坐标系中光线行进时间之比
+    ///
+    /// 物体坐标系中的行进时间等于场景坐标系中的行进时间乘以该比值
+    fn to_object(&self, ray: &Ray) -> (Ray, f64) {
+        let direction: Vec3 = self.inverse.transform_vec(ray.get_direction());
+        let object_ray = Ray::new_from(self.inverse.transform_point(ray.get_origin()), direction);
+        (object_ray, direction.magnitude())
+    }
+
+    /// 私有方法，把物体坐标系中的交点记录 `hit` 变换回场景坐标系
+    ///
+    /// 几何法向量与着色法向量分别变换，`front_face` 由几何法向量决定；
+    /// 交点记录带有发光体时，改为记录实例自身，使其与场景中登记的发光体一致
+    fn to_world<'a>(&'a self, ray: &Ray, hit: HitRecord<'a>, ratio: f64) -> HitRecord<'a> {
+        let outward = |normal: &Vec3| -> Vec3 {
+            let object_normal: Vec3 = if hit.is_front_face() { *normal } else { -normal };
+            self.normal_matrix.transform_vec(&object_normal).normalize()
+        };
+        let mut world_hit = HitRecord::new_from(
+            ray,
+            hit.get_t() / ratio,
+            outward(hit.get_normal()),
+            hit.get_uv(),
+            hit.get_texture(),
+        );
+        world_hit.set_shading_normal(outward(hit.get_shading_normal()));
+        world_hit.set_vertex_color(hit.get_vertex_color());
+        world_hit.set_medium(hit.get_medium());
+        if hit.get_emitter().is_some() {
+            world_hit.set_emitter(self.as_emitter());
+        }
+        world_hit
+    }
+
+    /// 私有方法，把物体坐标系中由 `object_origin` 看去、在 `object_point` 处以立体角计的概率密度
+    /// 换算为场景坐标系中由 `origin` 看去的概率密度
+    ///
+    /// `object_normal` 为物体坐标系中 `object_point` 处的单位法向量；
+    /// 面积元经变换后放大 `|det| · |M⁻ᵀ·n|` 倍，以面积计的概率密度相应缩小
+    fn pdf_to_world(
+        &self,
+        object_pdf: f64,
+        object_origin: &Coord3,
+        object_point: &Coord3,
+        object_normal: &Vec3,
+        origin: &Coord3,
+    ) -> f64 {
+        let transformed_normal: Vec3 = self.normal_matrix.transform_vec(object_normal);
+        let area_scale: f64 = self.transform.determinant().abs() * transformed_normal.magnitude();
+        let area_pdf: f64 = solid_angle_to_area(object_pdf, object_origin, object_point, object_normal) / area_scale;
+        let point: Coord3 = self.transform.transform_point(object_point);
+        area_to_solid_angle(area_pdf, origin, &point, &transformed_normal.normalize())
+    }
+}
+
+impl RayIntersectOpaque for Instance {
+    fn intersection(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<Option<HitRecord<'_>>, Box<dyn Error>> {
+        let (object_ray, ratio) = self.to_object(ray);
+        let object_bound: (f64, f64) = (t_bound.0 * ratio, t_bound.1 * ratio);
+        Ok(self
+            .object
+            .intersection(&object_ray, object_bound)?
+            .map(|hit| self.to_world(ray, hit, ratio)))
+    }
+}
+
+impl RayIntersectTransparent for Instance {
+    /// 被实例化的物体不可被穿透时返回 `None`
+    fn enter_n_exit(&self, ray: &Ray) -> Result<Option<(HitRecord<'_>, HitRecord<'_>)>, Box<dyn Error>> {
+        let transparent = match self.object.as_transparent() {
+            Some(transparent) => transparent,
+            None => return Ok(None),
+        };
+        let (object_ray, ratio) = self.to_object(ray);
+        Ok(transparent
+            .enter_n_exit(&object_ray)?
+            .map(|(enter, exit)| (self.to_world(ray, enter, ratio), self.to_world(ray, exit, ratio))))
+    }
+}
+
+impl Object for Instance {
+    fn hit(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<Option<HitRecord<'_>>, Box<dyn Error>> {
+        let (object_ray, ratio) = self.to_object(ray);
+        let object_bound: (f64, f64) = (t_bound.0 * ratio, t_bound.1 * ratio);
+        Ok(self
+            .object
+            .hit(&object_ray, object_bound)?
+            .map(|hit| self.to_world(ray, hit, ratio)))
+    }
+
+    fn any_hit(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<bool, Box<dyn Error>> {
+        let (object_ray, ratio) = self.to_object(ray);
+        self.object.any_hit(&object_ray, (t_bound.0 * ratio, t_bound.1 * ratio))
+    }
+
+    fn as_emitter(&self) -> Option<&dyn Emitter> {
+        match self.object.as_emitter() {
+            Some(_) => Some(self),
+            None => None,
+        }
+    }
+
+    fn as_transparent(&self) -> Option<&dyn RayIntersectTransparent> {
+        match self.object.as_transparent() {
+            Some(_) => Some(self),
+            None => None,
+        }
+    }
+}
+
+impl Emitter for Instance {
+    /// 在物体坐标系中由参考点对应的点采样，再把采样点与概率密度换算到场景坐标系
+    fn sample_emitter(&self, origin: &Coord3) -> Option<EmitterSample> {
+        let emitter: &dyn Emitter = self.object.as_emitter()?;
+        let object_origin: Coord3 = self.inverse.transform_point(origin);
+        let sample: EmitterSample = emitter.sample_emitter(&object_origin)?;
+        // 采样点处的法向量由参考点向采样点求交得到
+        let direction: Vec3 = (sample.get_point() - object_origin).normalize();
+        let hit: HitRecord<'_> = self.object.hit(&Ray::new_from(object_origin, direction), FORWARD_T_BOUND).ok()??;
+        let pdf: f64 =
+            self.pdf_to_world(sample.get_pdf(), &object_origin, sample.get_point(), hit.get_normal(), origin);
+        if pdf <= 0.0 {
+            return None;
+        }
+        Some(EmitterSample::new_from(
+            self.transform.transform_point(sample.get_point()),
+            pdf,
+            sample.get_radiance(),
+        ))
+    }
+
+    fn emitter_pdf(&self, origin: &Coord3, direction: &Vec3) -> f64 {
+        let emitter: &dyn Emitter = match self.object.as_emitter() {
+            Some(emitter) => emitter,
+            None => return 0.0,
+        };
+        let (object_ray, _) = self.to_object(&Ray::new_from(*origin, *direction));
+        let object_origin: &Coord3 = object_ray.get_origin();
+        let object_direction: Vec3 = object_ray.get_direction().normalize();
+        let object_pdf: f64 = emitter.emitter_pdf(object_origin, &object_direction);
+        if object_pdf <= 0.0 {
+            return 0.0;
+        }
+        match self.object.hit(&Ray::new_from(*object_origin, object_direction), FORWARD_T_BOUND) {
+            Ok(Some(hit)) => self.pdf_to_world(object_pdf, object_origin, hit.get_point(), hit.get_normal(), origin),
+            _ => 0.0,
+        }
+    }
+}
+
+impl Debug for Instance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Instance")
+            .field("transform", &self.transform)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Bounded for Instance {
+    /// 变换物体包围盒的 8 个角点后重新求轴对齐包围盒
+    fn bounding_box(&self) -> AlignedBox {
+        let bound: AlignedBox = self.object.bounding_box();
+        let (min, max) = (bound.get_min(), bound.get_max());
+        let corners: Vec<Coord3> = (0..8)
+            .map(|i| {
+                let pick = |bit: usize, lo: f64, hi: f64| -> f64 { if i & bit == 0 { lo } else { hi } };
+                self.transform.transform_point(&Coord3::new_from(
+                    pick(1, min.x(), max.x()),
+                    pick(2, min.y(), max.y()),
+                    pick(4, min.z(), max.z()),
+                ))
+            })
+            .collect();
+        corners[1..]
+            .iter()
+            .fold(AlignedBox::from_corners(&corners[0], &corners[0]), |bound, corner| {
+                bound.merge(&AlignedBox::from_corners(corner, corner))
+            })
+    }
+}
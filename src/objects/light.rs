@@ -0,0 +1,261 @@
+use std::error::Error;
+use std::fmt::{Debug, Display};
+
+use crate::basics::color::Color;
+use crate::basics::coord3::Coord3;
+use crate::basics::vec3::{Vec3, ZERO_VEC3};
+use crate::errors::{MainErr, nan};
+
+/// 光源对某一点的照明结果，由 `Light::illuminate` 生成
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct LightSample {
+    /// 从被照点指向光源的单位向量
+    direction: Vec3,
+    /// 被照点到光源的距离，平行光为 `f64::INFINITY`
+    distance: f64,
+    /// 到达被照点的辐射度（已计入衰减）
+    radiance: Color,
+}
+
+impl LightSample {
+    pub fn new_from(direction: Vec3, distance: f64, radiance: Color) -> Self {
+        Self {
+            direction: direction.normalize(),
+            distance,
+            radiance,
+        }
+    }
+
+    pub fn get_direction(&self) -> &Vec3 {
+        &self.direction
+    }
+
+    pub fn get_distance(&self) -> f64 {
+        self.distance
+    }
+
+    pub fn get_radiance(&self) -> Color {
+        self.radiance
+    }
+}
+
+/// 不占据空间的理想光源
+pub trait Light: Debug {
+    /// 光源对点 `point` 的照明；点不受该光源照射时返回 `None`
+    ///
+    /// 不考虑遮挡，遮挡由调用方以阴影光线检测
+    fn illuminate(&self, point: &Coord3) -> Option<LightSample>;
+}
+
+/// 点光源：向各方向均匀发光，强度按距离平方衰减
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct PointLight {
+    position: Coord3,
+    color: Color,
+    /// 距光源单位距离处的强度
+    intensity: f64,
+}
+
+impl PointLight {
+    /// `intensity` 为 `f64::NAN` 时返回 `MainErr`，为负时返回 `LightErr::InvalidParamErr`
+    pub fn new_from(position: Coord3, color: Color, intensity: f64) -> Result<Self, Box<dyn Error>> {
+        Ok(Self {
+            position,
+            color,
+            intensity: check_intensity(intensity, "PointLight::new_from")?,
+        })
+    }
+
+    pub fn get_position(&self) -> &Coord3 {
+        &self.position
+    }
+
+    pub fn get_color(&self) -> Color {
+        self.color
+    }
+
+    pub fn get_intensity(&self) -> f64 {
+        self.intensity
+    }
+}
+
+impl Light for PointLight {
+    fn illuminate(&self, point: &Coord3) -> Option<LightSample> {
+        let to_light: Vec3 = self.position - point;
+        let distance: f64 = to_light.magnitude();
+        if distance <= 0.0 {
+            return None;
+        }
+        let radiance: Color = self.color * (self.intensity / (distance * distance));
+        Some(LightSample::new_from(to_light, distance, radiance))
+    }
+}
+
+/// 平行光：来自无穷远处、方向一致且不衰减的光（如日光）
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct DirectionalLight {
+    /// 光的传播方向（单位向量）
+    direction: Vec3,
+    color: Color,
+    intensity: f64,
+}
+
+impl DirectionalLight {
+    /// `direction` 为光的传播方向，即从光源射向场景的方向
+    ///
+    /// `direction` 为零向量或 `intensity` 为负时返回 `LightErr::InvalidParamErr`
+    pub fn new_from(direction: Vec3, color: Color, intensity: f64) -> Result<Self, Box<dyn Error>> {
+        if direction == ZERO_VEC3 {
+            return Err(Box::new(LightErr::InvalidParamErr));
+        }
+        Ok(Self {
+            direction: direction.normalize(),
+            color,
+            intensity: check_intensity(intensity, "DirectionalLight::new_from")?,
+        })
+    }
+
+    pub fn get_direction(&self) -> &Vec3 {
+        &self.direction
+    }
+
+    pub fn get_color(&self) -> Color {
+        self.color
+    }
+
+    pub fn get_intensity(&self) -> f64 {
+        self.intensity
+    }
+}
+
+impl Light for DirectionalLight {
+    fn illuminate(&self, _point: &Coord3) -> Option<LightSample> {
+        Some(LightSample::new_from(
+            -self.direction,
+            f64::INFINITY,
+            self.color * self.intensity,
+        ))
+    }
+}
+
+/// 聚光灯：只照亮以 `direction` 为轴的圆锥内的点，强度按距离平方衰减
+///
+/// 偏离轴线的角度小于 `inner_angle` 时为全强度，大于 `outer_angle` 时不照明，
+/// 两者之间平滑过渡
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct SpotLight {
+    position: Coord3,
+    /// 圆锥轴线方向（单位向量）
+    direction: Vec3,
+    color: Color,
+    /// 距光源单位距离处轴线上的强度
+    intensity: f64,
+    /// 内锥半角的余弦
+    cos_inner: f64,
+    /// 外锥半角的余弦
+    cos_outer: f64,
+}
+
+impl SpotLight {
+    /// `inner_angle` 与 `outer_angle` 为圆锥半角（角度制），需满足 `0 <= inner_angle <= outer_angle < 180`
+    ///
+    /// 参数含 `f64::NAN` 时返回 `MainErr`，
+    /// `direction` 为零向量、`intensity` 为负或角度无效时返回 `LightErr::InvalidParamErr`
+    pub fn new_from(
+        position: Coord3,
+        direction: Vec3,
+        color: Color,
+        intensity: f64,
+        inner_angle: f64,
+        outer_angle: f64,
+    ) -> Result<Self, Box<dyn Error>> {
+        let inner_angle: f64 = nan::check::<MainErr>(inner_angle, "SpotLight::new_from")?;
+        let outer_angle: f64 = nan::check::<MainErr>(outer_angle, "SpotLight::new_from")?;
+        if direction == ZERO_VEC3 || inner_angle < 0.0 || inner_angle > outer_angle || outer_angle >= 180.0 {
+            return Err(Box::new(LightErr::InvalidParamErr));
+        }
+        Ok(Self {
+            position,
+            direction: direction.normalize(),
+            color,
+            intensity: check_intensity(intensity, "SpotLight::new_from")?,
+            cos_inner: inner_angle.to_radians().cos(),
+            cos_outer: outer_angle.to_radians().cos(),
+        })
+    }
+
+    pub fn get_position(&self) -> &Coord3 {
+        &self.position
+    }
+
+    pub fn get_direction(&self) -> &Vec3 {
+        &self.direction
+    }
+
+    pub fn get_color(&self) -> Color {
+        self.color
+    }
+
+    pub fn get_intensity(&self) -> f64 {
+        self.intensity
+    }
+
+    /// 内锥与外锥半角（角度制）
+    pub fn get_angles(&self) -> (f64, f64) {
+        (self.cos_inner.acos().to_degrees(), self.cos_outer.acos().to_degrees())
+    }
+}
+
+impl Light for SpotLight {
+    fn illuminate(&self, point: &Coord3) -> Option<LightSample> {
+        let to_light: Vec3 = self.position - point;
+        let distance: f64 = to_light.magnitude();
+        if distance <= 0.0 {
+            return None;
+        }
+        let cos_theta: f64 = -(to_light.normalize() * self.direction);
+        if cos_theta <= self.cos_outer {
+            return None;
+        }
+        let falloff: f64 = if cos_theta >= self.cos_inner {
+            1.0
+        } else {
+            // smoothstep 过渡
+            let x: f64 = (cos_theta - self.cos_outer) / (self.cos_inner - self.cos_outer);
+            x * x * (3.0 - 2.0 * x)
+        };
+        let radiance: Color = self.color * (self.intensity * falloff / (distance * distance));
+        Some(LightSample::new_from(to_light, distance, radiance))
+    }
+}
+
+/// 私有函数，检查光源强度
+fn check_intensity(intensity: f64, val_details: &str) -> Result<f64, Box<dyn Error>> {
+    let intensity: f64 = nan::check::<MainErr>(intensity, val_details)?;
+    if intensity < 0.0 {
+        return Err(Box::new(LightErr::InvalidParamErr));
+    }
+    Ok(intensity)
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum LightErr {
+    /// 输入了无效的光源参数
+    InvalidParamErr,
+}
+
+impl Display for LightErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidParamErr => write!(f, "invalid light parameter"),
+        }
+    }
+}
+
+impl Error for LightErr {}
+
+impl LightErr {
+    pub fn handle(&self) {
+        eprintln!("[Light Error] {}", self);
+    }
+}
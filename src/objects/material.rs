@@ -0,0 +1,154 @@
+use std::f64::consts::PI;
+
+use crate::basics::color::{BLACK, Color};
+use crate::basics::random;
+use crate::basics::vec3::Vec3;
+use crate::rays::hit::HitRecord;
+use crate::rays::ray::Ray;
+
+/// 一次散射的结果，由 `Scatter::scatter` 生成
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct ScatterRecord {
+    /// 衰减，即 BSDF 与余弦项之积除以采样概率密度
+    attenuation: Color,
+    /// 散射光线
+    ray: Ray,
+    /// 散射方向是否由镜面（δ 分布）采样得到，此时无法与光源采样做多重重要性采样
+    specular: bool,
+}
+
+impl ScatterRecord {
+    pub fn new_from(attenuation: Color, ray: Ray, specular: bool) -> Self {
+        Self {
+            attenuation,
+            ray,
+            specular,
+        }
+    }
+
+    pub fn get_attenuation(&self) -> Color {
+        self.attenuation
+    }
+
+    pub fn get_ray(&self) -> &Ray {
+        &self.ray
+    }
+
+    pub fn is_specular(&self) -> bool {
+        self.specular
+    }
+}
+
+/// 光线在物体表面的散射模型
+pub trait Scatter {
+    /// 光线 `ray` 在交点 `hit` 处散射，`albedo` 为交点处的表面颜色
+    ///
+    /// 光线被吸收时返回 `None`
+    fn scatter(&self, ray: &Ray, hit: &HitRecord<'_>, albedo: Color) -> Option<ScatterRecord>;
+
+    /// 光线 `ray` 在交点 `hit` 处向单位向量 `direction` 散射时 BSDF 与余弦项之积
+    ///
+    /// 只计算非镜面部分，镜面散射模型返回黑色
+    fn eval(&self, _ray: &Ray, _hit: &HitRecord<'_>, _direction: &Vec3, _albedo: Color) -> Color {
+        BLACK
+    }
+
+    /// `scatter` 采样得到方向 `direction` 的概率密度（以立体角计）
+    ///
+    /// 只计算非镜面部分，镜面散射模型返回 `0.0`
+    fn pdf(&self, _ray: &Ray, _hit: &HitRecord<'_>, _direction: &Vec3) -> f64 {
+        0.0
+    }
+}
+
+/// 理想漫反射（Lambertian）：散射方向按法线附近的余弦分布随机选取
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Default)]
+pub struct Lambertian;
+
+impl Scatter for Lambertian {
+    fn scatter(&self, _ray: &Ray, hit: &HitRecord<'_>, albedo: Color) -> Option<ScatterRecord> {
+        let normal: &Vec3 = hit.get_shading_normal();
+        let direction: Vec3 = normal + random::random_unit_vec3();
+        // 随机向量与法线几乎相反时退化为沿法线散射
+        let direction: Vec3 = if direction.magnitude() < 1e-8 { *normal } else { direction };
+        Some(ScatterRecord::new_from(albedo, Ray::new_from(*hit.get_point(), direction), false))
+    }
+
+    fn eval(&self, _ray: &Ray, hit: &HitRecord<'_>, direction: &Vec3, albedo: Color) -> Color {
+        (direction * hit.get_shading_normal()).max(0.0) / PI * albedo
+    }
+
+    fn pdf(&self, _ray: &Ray, hit: &HitRecord<'_>, direction: &Vec3) -> f64 {
+        (direction * hit.get_shading_normal()).max(0.0) / PI
+    }
+}
+
+/// 金属：镜面反射方向再叠加半径为 `fuzz` 的随机扰动
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct Metal {
+    /// 模糊度，取值介于 0.0（理想镜面）到 1.0
+    fuzz: f64,
+}
+
+impl Metal {
+    /// `fuzz` 会被截断到 `[0, 1]`
+    pub fn new_from(fuzz: f64) -> Self {
+        Self {
+            fuzz: fuzz.clamp(0.0, 1.0),
+        }
+    }
+
+    pub fn get_fuzz(&self) -> f64 {
+        self.fuzz
+    }
+}
+
+impl Scatter for Metal {
+    /// 扰动后的方向落到表面以下时，光线被吸收；带扰动的反射也按镜面处理
+    fn scatter(&self, ray: &Ray, hit: &HitRecord<'_>, albedo: Color) -> Option<ScatterRecord> {
+        let reflected: Vec3 = ray.get_direction().reflect(hit.get_shading_normal());
+        let direction: Vec3 = reflected + self.fuzz * random::random_unit_vec3();
+        if direction * hit.get_shading_normal() <= 0.0 {
+            return None;
+        }
+        Some(ScatterRecord::new_from(albedo, Ray::new_from(*hit.get_point(), direction), true))
+    }
+}
+
+/// 电介质（玻璃、水等）：按 Snell 定律折射，按 Schlick 近似的 Fresnel 系数随机选择反射
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct Dielectric {
+    /// 相对于外部介质（空气）的折射率
+    ior: f64,
+}
+
+impl Dielectric {
+    pub fn new_from(ior: f64) -> Self {
+        Self { ior }
+    }
+
+    pub fn get_ior(&self) -> f64 {
+        self.ior
+    }
+
+    /// Schlick 近似的反射率
+    pub fn schlick(cos_theta: f64, eta_ratio: f64) -> f64 {
+        let r0: f64 = ((1.0 - eta_ratio) / (1.0 + eta_ratio)).powi(2);
+        r0 + (1.0 - r0) * (1.0 - cos_theta).powi(5)
+    }
+}
+
+impl Scatter for Dielectric {
+    fn scatter(&self, ray: &Ray, hit: &HitRecord<'_>, albedo: Color) -> Option<ScatterRecord> {
+        let eta_ratio: f64 = if hit.is_front_face() { 1.0 / self.ior } else { self.ior };
+        let direction: &Vec3 = ray.get_direction();
+        let normal: &Vec3 = hit.get_shading_normal();
+        let cos_theta: f64 = (-(direction * normal)).min(1.0);
+
+        let scattered: Vec3 = match direction.refract(normal, eta_ratio) {
+            Some(refracted) if random::random_f64() >= Self::schlick(cos_theta, eta_ratio) => refracted,
+            _ => direction.reflect(normal),
+        };
+        Some(ScatterRecord::new_from(albedo, Ray::new_from(*hit.get_point(), scattered), true))
+    }
+}
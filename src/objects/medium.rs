@@ -0,0 +1,219 @@
+use std::error::Error;
+use std::f64::consts::PI;
+use std::fmt::{Debug, Display};
+
+use crate::basics::color::Color;
+use crate::basics::random;
+use crate::basics::vec3::Vec3;
+use crate::errors::{MainErr, nan};
+use crate::objects::alignedbox::AlignedBox;
+use crate::objects::{Bounded, Object};
+use crate::rays::hit::HitRecord;
+use crate::rays::ray::{Ray, RayIntersectOpaque};
+
+/// 相函数：参与介质中光线散射方向的分布
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub enum PhaseFunction {
+    /// 各向同性散射
+    Isotropic,
+    /// Henyey-Greenstein 相函数，参数为不对称因子 `g`，取值需在 `(-1, 1)` 内；
+    /// 正值偏向前向散射，负值偏向后向散射，`0.0` 即各向同性
+    HenyeyGreenstein(f64),
+}
+
+impl PhaseFunction {
+    /// 沿单位向量 `direction` 传播的光线散射后沿单位向量 `scattered` 传播的概率密度（以立体角计）
+    ///
+    /// 相函数已归一化，其取值即为 `sample` 的概率密度
+    pub fn eval(&self, direction: &Vec3, scattered: &Vec3) -> f64 {
+        match self {
+            Self::Isotropic => 1.0 / (4.0 * PI),
+            Self::HenyeyGreenstein(g) => {
+                let cos_theta: f64 = direction * scattered;
+                let denom: f64 = 1.0 + g * g - 2.0 * g * cos_theta;
+                (1.0 - g * g) / (4.0 * PI * denom * denom.sqrt())
+            }
+        }
+    }
+
+    /// 按相函数为沿单位向量 `direction` 传播的光线采样散射方向
+    pub fn sample(&self, direction: &Vec3) -> Vec3 {
+        let cos_theta: f64 = match self {
+            Self::HenyeyGreenstein(g) if g.abs() >= 1e-3 => {
+                let s: f64 = (1.0 - g * g) / (1.0 - g + 2.0 * g * random::random_f64());
+                (1.0 + g * g - s * s) / (2.0 * g)
+            }
+            _ => 1.0 - 2.0 * random::random_f64(),
+        };
+        let sin_theta: f64 = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+        let phi: f64 = 2.0 * PI * random::random_f64();
+        let (u, v) = direction.orthonormal_basis();
+        sin_theta * phi.cos() * u + sin_theta * phi.sin() * v + cos_theta * direction
+    }
+}
+
+/// 密度均匀的参与介质（雾、烟等），充满一个可被穿透的封闭物体
+///
+/// 光线在介质中行进时按指数分布采样自由程：在离开边界前发生碰撞时，
+/// 交点记录位于介质内部，并由 `HitRecord::get_medium` 指向本介质，由积分器按相函数散射
+pub struct ConstantMedium {
+    /// 介质边界，需为凸的封闭物体（如 `OpaqueSphere`、`AlignedBox`）
+    boundary: Box<dyn Object>,
+    /// 吸收系数 σa
+    absorption: f64,
+    /// 散射系数 σs
+    scattering: f64,
+    /// 散射光的颜色
+    color: Color,
+    phase: PhaseFunction,
+}
+
+impl ConstantMedium {
+    /// 由边界物体、吸收系数、散射系数、散射光颜色与相函数创建介质
+    ///
+    /// 边界物体不可被穿透（`Object::as_transparent` 返回 `None`）时返回 `MediumErr::OpenBoundaryErr`；
+    /// 参数含 `f64::NAN` 时返回 `MainErr`；系数为负、两系数均为零或 `g` 超出取值范围时返回 `MediumErr::InvalidParamErr`
+    pub fn new_from(
+        boundary: Box<dyn Object>,
+        absorption: f64,
+        scattering: f64,
+        color: Color,
+        phase: PhaseFunction,
+    ) -> Result<Self, Box<dyn Error>> {
+        if boundary.as_transparent().is_none() {
+            return Err(Box::new(MediumErr::OpenBoundaryErr));
+        }
+        let absorption: f64 = nan::check::<MainErr>(absorption, "ConstantMedium::new_from")?;
+        let scattering: f64 = nan::check::<MainErr>(scattering, "ConstantMedium::new_from")?;
+        if absorption < 0.0 || scattering < 0.0 || absorption + scattering <= 0.0 {
+            return Err(Box::new(MediumErr::InvalidParamErr));
+        }
+        if let PhaseFunction::HenyeyGreenstein(g) = phase {
+            let g: f64 = nan::check::<MainErr>(g, "ConstantMedium::new_from")?;
+            if g <= -1.0 || g >= 1.0 {
+                return Err(Box::new(MediumErr::InvalidParamErr));
+            }
+        }
+        Ok(Self {
+            boundary,
+            absorption,
+            scattering,
+            color,
+            phase,
+        })
+    }
+
+    pub fn get_boundary(&self) -> &dyn Object {
+        self.boundary.as_ref()
+    }
+
+    pub fn get_absorption(&self) -> f64 {
+        self.absorption
+    }
+
+    pub fn get_scattering(&self) -> f64 {
+        self.scattering
+    }
+
+    pub fn get_color(&self) -> Color {
+        self.color
+    }
+
+    pub fn get_phase(&self) -> PhaseFunction {
+        self.phase
+    }
+
+    /// 消光系数 σt = σa + σs
+    pub fn density(&self) -> f64 {
+        self.absorption + self.scattering
+    }
+
+    /// 碰撞时发生散射（而非被吸收）的比例乘以散射光颜色
+    pub fn albedo(&self) -> Color {
+        self.color * (self.scattering / self.density())
+    }
+
+    /// 光线在介质中行进 `length` 后未发生碰撞的概率（透射率）
+    pub fn transmittance(&self, length: f64) -> f64 {
+        (-self.density() * length).exp()
+    }
+
+    /// 按消光系数采样自由程，即光线在介质中行进多远后发生碰撞；超出 `length` 时返回 `None`
+    pub fn sample_distance(&self, length: f64) -> Option<f64> {
+        let distance: f64 = -(1.0 - random::random_f64()).ln() / self.density();
+        if distance < length { Some(distance) } else { None }
+    }
+}
+
+impl Debug for ConstantMedium {
+    /// 边界物体以其包围盒表示
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConstantMedium")
+            .field("boundary", &self.boundary.bounding_box())
+            .field("absorption", &self.absorption)
+            .field("scattering", &self.scattering)
+            .field("color", &self.color)
+            .field("phase", &self.phase)
+            .finish()
+    }
+}
+
+impl RayIntersectOpaque for ConstantMedium {
+    /// 在光线位于介质内、且落在 `t_bound` 内的一段上采样碰撞点
+    ///
+    /// 交点记录的法向量与光线方向相反，不带材质
+    fn intersection(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<Option<HitRecord<'_>>, Box<dyn Error>> {
+        let transparent = match self.boundary.as_transparent() {
+            Some(transparent) => transparent,
+            None => return Err(Box::new(MediumErr::OpenBoundaryErr)),
+        };
+        let (enter, exit) = match transparent.enter_n_exit(ray)? {
+            Some(hits) => hits,
+            None => return Ok(None),
+        };
+        let t_enter: f64 = enter.get_t().max(t_bound.0);
+        let t_exit: f64 = exit.get_t().min(t_bound.1);
+        if t_enter >= t_exit {
+            return Ok(None);
+        }
+
+        Ok(self.sample_distance(t_exit - t_enter).map(|distance| {
+            let mut hit = HitRecord::new_from(ray, t_enter + distance, -ray.get_direction(), (0.0, 0.0), None);
+            hit.set_medium(Some(self));
+            hit
+        }))
+    }
+}
+
+impl Bounded for ConstantMedium {
+    fn bounding_box(&self) -> AlignedBox {
+        self.boundary.bounding_box()
+    }
+}
+
+impl Object for ConstantMedium {}
+
+#[derive(Debug, Clone, Copy)]
+pub enum MediumErr {
+    /// 输入了无效的介质参数
+    InvalidParamErr,
+    /// 边界物体不是可被穿透的封闭物体
+    OpenBoundaryErr,
+}
+
+impl Display for MediumErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidParamErr => write!(f, "invalid medium parameter"),
+            Self::OpenBoundaryErr => write!(f, "medium boundary is not a closed transparent object"),
+        }
+    }
+}
+
+impl Error for MediumErr {}
+
+impl MediumErr {
+    pub fn handle(&self) {
+        eprintln!("[Medium Error] {}", self);
+    }
+}
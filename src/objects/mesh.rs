@@ -0,0 +1,367 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::sync::Arc;
+
+use crate::basics::color::Color;
+use crate::basics::coord3::Coord3;
+use crate::basics::vec3::{Vec3, ZERO_VEC3};
+use crate::basics::tree::BiTree;
+use crate::objects::alignedbox::AlignedBox;
+use crate::objects::bvh::{Bvh, BvhNode};
+use crate::objects::texture::OpaqueTexture;
+use crate::objects::triangle::{
+    OpaqueTriangle, interpolate_color, interpolate_normal, interpolate_uv, moller_trumbore, triangle_bounding_box,
+};
+use crate::objects::{Bounded, Object};
+use crate::rays::hit::HitRecord;
+use crate::rays::ray::{Ray, RayIntersectOpaque, RayIntersectTransparent};
+
+/// 网格的顶点缓冲：顶点坐标、顶点法向量、纹理坐标与顶点颜色，由各三角面按索引引用
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct MeshBuffers {
+    positions: Vec<Coord3>,
+    normals: Vec<Vec3>,
+    uvs: Vec<(f64, f64)>,
+    /// 顶点颜色，为空或与 `positions` 一一对应，与顶点坐标使用同一组索引
+    colors: Vec<Color>,
+}
+
+impl MeshBuffers {
+    pub fn new_from(positions: Vec<Coord3>, normals: Vec<Vec3>, uvs: Vec<(f64, f64)>) -> Self {
+        Self {
+            positions,
+            normals,
+            uvs,
+            colors: Vec::new(),
+        }
+    }
+
+    pub fn get_positions(&self) -> &[Coord3] {
+        &self.positions
+    }
+
+    pub fn get_normals(&self) -> &[Vec3] {
+        &self.normals
+    }
+
+    pub fn get_uvs(&self) -> &[(f64, f64)] {
+        &self.uvs
+    }
+
+    pub fn get_colors(&self) -> &[Color] {
+        &self.colors
+    }
+
+    pub fn set_colors(&mut self, colors: Vec<Color>) -> &mut Self {
+        self.colors = colors;
+        self
+    }
+}
+
+/// 网格中的一个三角面，保存三个顶点在 `MeshBuffers` 各缓冲中的索引（0 索引）
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct MeshFace {
+    positions: [usize; 3],
+    normals: Option<[usize; 3]>,
+    uvs: Option<[usize; 3]>,
+}
+
+impl MeshFace {
+    pub fn new_from(positions: [usize; 3], normals: Option<[usize; 3]>, uvs: Option<[usize; 3]>) -> Self {
+        Self {
+            positions,
+            normals,
+            uvs,
+        }
+    }
+
+    pub fn get_positions(&self) -> [usize; 3] {
+        self.positions
+    }
+
+    pub fn get_normals(&self) -> Option<[usize; 3]> {
+        self.normals
+    }
+
+    pub fn get_uvs(&self) -> Option<[usize; 3]> {
+        self.uvs
+    }
+}
+
+/// 三角网格：各三角面共享同一组顶点缓冲，并在内部以 BVH 组织以加速求交
+///
+/// 所有三角面共用同一材质；三角面带有顶点法向量时，交点的着色法向量由其按重心坐标插值，
+/// 纹理坐标同理，三角面没有纹理坐标时取重心坐标 `(b1, b2)`；
+/// 顶点缓冲带有顶点颜色时，其插值与材质颜色相乘作为反照率
+///
+/// 封闭的网格可被穿透（见 `RayIntersectTransparent`），可用作玻璃等透明物体或参与介质的边界
+///
+/// 自发光的网格被光线击中时照常发光，但不会被登记为可直接采样的发光体
+pub struct TriangleMesh {
+    buffers: Arc<MeshBuffers>,
+    faces: Vec<MeshFace>,
+    bvh: Bvh,
+    texture: Option<OpaqueTexture>,
+}
+
+impl TriangleMesh {
+    /// 由顶点缓冲与三角面创建网格
+    ///
+    /// 面积为零的三角面会被忽略；索引越界或顶点颜色与顶点坐标数目不符时返回 `MeshErr::InvalidIndexErr`，
+    /// 没有有效的三角面时返回 `MeshErr::EmptyMeshErr`
+    pub fn new_from(buffers: MeshBuffers, faces: Vec<MeshFace>) -> Result<Self, Box<dyn Error>> {
+        Self::from_shared(Arc::new(buffers), faces)
+    }
+
+    /// 由与其他网格共享的顶点缓冲创建网格，例如同一 OBJ 文件中的不同分组
+    ///
+    /// 错误同 `new_from`
+    pub fn from_shared(buffers: Arc<MeshBuffers>, faces: Vec<MeshFace>) -> Result<Self, Box<dyn Error>> {
+        let in_range = |indices: Option<[usize; 3]>, len: usize| -> bool {
+            indices.is_none_or(|indices| indices.iter().all(|&i| i < len))
+        };
+        if !buffers.colors.is_empty() && buffers.colors.len() != buffers.positions.len() {
+            return Err(Box::new(MeshErr::InvalidIndexErr));
+        }
+        for face in &faces {
+            if !in_range(Some(face.positions), buffers.positions.len())
+                || !in_range(face.normals, buffers.normals.len())
+                || !in_range(face.uvs, buffers.uvs.len())
+            {
+                return Err(Box::new(MeshErr::InvalidIndexErr));
+            }
+        }
+
+        let faces: Vec<MeshFace> = faces
+            .into_iter()
+            .filter(|face| {
+                let [i1, i2, i3] = face.positions.map(|i| buffers.positions[i]);
+                (i2 - i1).cross(&(i3 - i1)) != ZERO_VEC3
+            })
+            .collect();
+        if faces.is_empty() {
+            return Err(Box::new(MeshErr::EmptyMeshErr));
+        }
+
+        let triangles: Vec<Box<dyn Object>> = faces
+            .iter()
+            .map(|&face| -> Box<dyn Object> {
+                Box::new(MeshTriangle {
+                    buffers: Arc::clone(&buffers),
+                    face,
+                })
+            })
+            .collect();
+        Ok(Self {
+            bvh: Bvh::new_from(triangles)?,
+            buffers,
+            faces,
+            texture: None,
+        })
+    }
+
+    pub fn get_buffers(&self) -> &Arc<MeshBuffers> {
+        &self.buffers
+    }
+
+    pub fn get_faces(&self) -> &[MeshFace] {
+        &self.faces
+    }
+
+    pub fn get_texture(&self) -> Option<&OpaqueTexture> {
+        self.texture.as_ref()
+    }
+
+    pub fn set_texture(&mut self, texture: OpaqueTexture) -> &mut Self {
+        self.texture = Some(texture);
+        self
+    }
+
+    /// 把各三角面展开为独立的 `OpaqueTriangle`，保留顶点法向量、纹理坐标、顶点颜色与网格的材质
+    pub fn to_triangles(&self) -> Vec<OpaqueTriangle> {
+        let buffers: &MeshBuffers = &self.buffers;
+        self.faces
+            .iter()
+            .filter_map(|face| {
+                let [p1, p2, p3] = face.positions.map(|i| buffers.positions[i]);
+                // 面积为零的三角面已在建立网格时被剔除
+                let mut triangle: OpaqueTriangle = OpaqueTriangle::new_from(p1, p2, p3).ok()?;
+                if let Some(indices) = face.normals {
+                    triangle.set_vertex_normals(indices.map(|i| buffers.normals[i]));
+                }
+                if let Some(indices) = face.uvs {
+                    triangle.set_vertex_uvs(indices.map(|i| buffers.uvs[i]));
+                }
+                if !buffers.colors.is_empty() {
+                    triangle.set_vertex_colors(face.positions.map(|i| buffers.colors[i]));
+                }
+                if let Some(texture) = &self.texture {
+                    triangle.set_texture(texture.clone());
+                }
+                Some(triangle)
+            })
+            .collect()
+    }
+}
+
+impl RayIntersectOpaque for TriangleMesh {
+    fn intersection(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<Option<HitRecord<'_>>, Box<dyn Error>> {
+        Ok(self.bvh.intersection(ray, t_bound)?.map(|mut hit| {
+            hit.set_texture(self.texture.as_ref());
+            hit
+        }))
+    }
+}
+
+impl Bounded for TriangleMesh {
+    fn bounding_box(&self) -> AlignedBox {
+        self.bvh.bounding_box()
+    }
+}
+
+impl RayIntersectTransparent for TriangleMesh {
+    /// 取光线所在直线与网格的第一个与最后一个交点，网格需为封闭网格
+    fn enter_n_exit(&self, ray: &Ray) -> Result<Option<(HitRecord<'_>, HitRecord<'_>)>, Box<dyn Error>> {
+        let line: (f64, f64) = (f64::NEG_INFINITY, f64::INFINITY);
+        let tree: &BiTree<BvhNode> = self.bvh.get_tree();
+        match (extreme_crossing(tree, ray, line, false)?, extreme_crossing(tree, ray, line, true)?) {
+            (Some(mut enter), Some(mut exit)) if exit.get_t() > 0.0 && enter.get_t() < exit.get_t() => {
+                enter.set_texture(self.texture.as_ref());
+                exit.set_texture(self.texture.as_ref());
+                Ok(Some((enter, exit)))
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+impl Object for TriangleMesh {
+    fn any_hit(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<bool, Box<dyn Error>> {
+        self.bvh.any_hit(ray, t_bound)
+    }
+
+    fn as_transparent(&self) -> Option<&dyn RayIntersectTransparent> {
+        Some(self)
+    }
+}
+
+/// 私有函数，光线所在直线在 `t_bound` 区间内与网格 BVH 中三角面的交点里，
+/// 行进时间最小（`last` 为 `false`）或最大（`last` 为 `true`）的交点记录
+///
+/// 每个三角面与直线至多有一个交点，因此在区间内求最近交点即得到该三角面唯一的交点；
+/// 与 `Bvh` 的最近交点查询不同，包围盒位于光线后方时不会被剔除
+fn extreme_crossing<'a>(
+    node: &'a BiTree<BvhNode>,
+    ray: &Ray,
+    mut t_bound: (f64, f64),
+    last: bool,
+) -> Result<Option<HitRecord<'a>>, Box<dyn Error>> {
+    match node.get_parent().get_bound().line_interval(ray) {
+        Some((t_enter, t_exit)) if t_enter <= t_bound.1 && t_exit >= t_bound.0 => {}
+        _ => return Ok(None),
+    }
+
+    let mut extreme: Option<HitRecord<'a>> = None;
+    let mut record = |hit: HitRecord<'a>, t_bound: &mut (f64, f64)| {
+        if last {
+            t_bound.0 = hit.get_t();
+        } else {
+            t_bound.1 = hit.get_t();
+        }
+        extreme = Some(hit);
+    };
+    for object in node.get_parent().get_objects() {
+        if let Some(hit) = object.intersection(ray, t_bound)? {
+            record(hit, &mut t_bound);
+        }
+    }
+    for child in [node.get_lchild(), node.get_rchild()].into_iter().flatten() {
+        if let Some(hit) = extreme_crossing(child, ray, t_bound, last)? {
+            record(hit, &mut t_bound);
+        }
+    }
+    Ok(extreme)
+}
+
+/// 网格内部的单个三角面，仅供网格的 BVH 使用，交点记录不含材质
+struct MeshTriangle {
+    buffers: Arc<MeshBuffers>,
+    face: MeshFace,
+}
+
+impl MeshTriangle {
+    /// 私有方法，三个顶点的坐标
+    fn points(&self) -> (&Coord3, &Coord3, &Coord3) {
+        let [i1, i2, i3] = self.face.positions;
+        let positions: &[Coord3] = &self.buffers.positions;
+        (&positions[i1], &positions[i2], &positions[i3])
+    }
+}
+
+impl RayIntersectOpaque for MeshTriangle {
+    fn intersection(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<Option<HitRecord<'_>>, Box<dyn Error>> {
+        let points = self.points();
+        let (t, b1, b2) = match moller_trumbore(points, ray, t_bound) {
+            Some(res) => res,
+            None => return Ok(None),
+        };
+        let (p1, p2, p3) = points;
+        let outward_normal: Vec3 = (p2 - p1).cross(&(p3 - p1)).normalize();
+        let uv: (f64, f64) = match self.face.uvs {
+            Some(indices) => interpolate_uv(&indices.map(|i| self.buffers.uvs[i]), b1, b2),
+            None => (b1, b2),
+        };
+        let mut hit = HitRecord::new_from(ray, t, outward_normal, uv, None);
+        let shading_normal: Option<Vec3> = self
+            .face
+            .normals
+            .and_then(|indices| interpolate_normal(&indices.map(|i| self.buffers.normals[i]), b1, b2));
+        if let Some(shading_normal) = shading_normal {
+            hit.set_shading_normal(shading_normal);
+        }
+        if !self.buffers.colors.is_empty() {
+            let colors: [Color; 3] = self.face.positions.map(|i| self.buffers.colors[i]);
+            hit.set_vertex_color(Some(interpolate_color(&colors, b1, b2)));
+        }
+        Ok(Some(hit))
+    }
+}
+
+impl Bounded for MeshTriangle {
+    fn bounding_box(&self) -> AlignedBox {
+        triangle_bounding_box(self.points())
+    }
+}
+
+impl Object for MeshTriangle {}
+
+#[derive(Debug, Clone, Copy)]
+pub enum MeshErr {
+    /// 三角面引用的顶点索引越界
+    InvalidIndexErr,
+    /// 网格不含有效的三角面
+    EmptyMeshErr,
+    /// OBJ 文件第 `n` 行（1 索引）格式无效
+    InvalidObjErr(usize),
+    /// PLY 文件格式无效或含不支持的内容
+    InvalidPlyErr,
+}
+
+impl Display for MeshErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidIndexErr => write!(f, "mesh face index out of range"),
+            Self::EmptyMeshErr => write!(f, "mesh has no valid face"),
+            Self::InvalidObjErr(line) => write!(f, "invalid obj content at line {}", line),
+            Self::InvalidPlyErr => write!(f, "invalid or unsupported ply content"),
+        }
+    }
+}
+
+impl Error for MeshErr {}
+
+impl MeshErr {
+    pub fn handle(&self) {
+        eprintln!("[Mesh Error] {}", self);
+    }
+}
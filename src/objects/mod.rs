@@ -1,7 +1,105 @@
+pub mod emitter;
+pub mod imagetexture;
+pub mod light;
+pub mod material;
+pub mod medium;
+pub mod pattern;
 pub mod texture;
 
 pub mod alignedbox;
 pub mod sphere;
 pub mod triangle;
+pub mod plane;
+pub mod disk;
+pub mod quad;
+pub mod cylinder;
+pub mod cone;
+pub mod torus;
+pub mod mesh;
+pub mod obj;
+pub mod ply;
 
-pub trait Object {}
+pub mod bvh;
+pub mod instance;
+pub mod world;
+
+use std::error::Error;
+use std::sync::Arc;
+
+use crate::rays::hit::HitRecord;
+use crate::rays::ray::{Ray, RayIntersectErr, RayIntersectOpaque, RayIntersectTransparent};
+use alignedbox::AlignedBox;
+use emitter::Emitter;
+
+/// 可求轴对齐包围盒的物体
+pub trait Bounded {
+    /// 完整包围物体的轴对齐包围盒
+    fn bounding_box(&self) -> AlignedBox;
+}
+
+/// 可放入场景容器的物体
+pub trait Object: RayIntersectOpaque + Bounded {
+    /// 光线在 `t_bound` 区间内与物体最近的交点记录
+    ///
+    /// 与 `intersection` 不同，光线由物体内部发出时不返回 `RayIntersectErr::InnerRayErr`：
+    /// 物体可被穿透（`as_transparent` 返回 `Some`）时返回光线离开物体的交点，否则视为不相交
+    fn hit(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<Option<HitRecord<'_>>, Box<dyn Error>> {
+        match self.intersection(ray, t_bound) {
+            Err(e) if matches!(e.downcast_ref::<RayIntersectErr>(), Some(RayIntersectErr::InnerRayErr)) => {
+                match self.as_transparent() {
+                    Some(transparent) => transparent.surface_hit(ray, t_bound),
+                    None => Ok(None),
+                }
+            }
+            res => res,
+        }
+    }
+
+    /// 光线在 `t_bound` 区间内是否与物体相交（阴影光线等只关心遮挡与否的查询）
+    ///
+    /// 光线由物体内部发出时同 `hit`
+    fn any_hit(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<bool, Box<dyn Error>> {
+        Ok(self.hit(ray, t_bound)?.is_some())
+    }
+
+    /// 物体自发光且可被直接采样时，返回其作为发光体的引用
+    fn as_emitter(&self) -> Option<&dyn Emitter> {
+        None
+    }
+
+    /// 物体为封闭实心体、光线可由内部发出并穿透时，返回其穿透求交的引用
+    fn as_transparent(&self) -> Option<&dyn RayIntersectTransparent> {
+        None
+    }
+}
+
+/// 共享所有权的物体，供场景容器同时在物体列表与发光体列表中持有同一物体
+impl RayIntersectOpaque for Arc<dyn Object> {
+    fn intersection(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<Option<HitRecord<'_>>, Box<dyn Error>> {
+        self.as_ref().intersection(ray, t_bound)
+    }
+}
+
+impl Bounded for Arc<dyn Object> {
+    fn bounding_box(&self) -> AlignedBox {
+        self.as_ref().bounding_box()
+    }
+}
+
+impl Object for Arc<dyn Object> {
+    fn hit(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<Option<HitRecord<'_>>, Box<dyn Error>> {
+        self.as_ref().hit(ray, t_bound)
+    }
+
+    fn any_hit(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<bool, Box<dyn Error>> {
+        self.as_ref().any_hit(ray, t_bound)
+    }
+
+    fn as_emitter(&self) -> Option<&dyn Emitter> {
+        self.as_ref().as_emitter()
+    }
+
+    fn as_transparent(&self) -> Option<&dyn RayIntersectTransparent> {
+        self.as_ref().as_transparent()
+    }
+}
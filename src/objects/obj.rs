@@ -0,0 +1,231 @@
+use std::error::Error;
+use std::fs;
+use std::sync::Arc;
+
+use crate::basics::coord3::Coord3;
+use crate::basics::vec3::Vec3;
+use crate::objects::mesh::{MeshBuffers, MeshErr, MeshFace, TriangleMesh};
+
+impl TriangleMesh {
+    /// 读取 Wavefront OBJ 文件 `path`，所有分组合并为一个网格
+    ///
+    /// 支持 `v`、`vt`、`vn`、`f`（`i`、`i/j`、`i//k`、`i/j/k` 及负数相对索引）与 `g`、`o` 语句，
+    /// 多边形面按扇形剖分为三角面，其余语句（材质、平滑组等）被忽略
+    ///
+    /// 文件格式无效时返回 `MeshErr::InvalidObjErr`，其余错误同 `TriangleMesh::new_from`
+    pub fn load_obj(path: &str) -> Result<Self, Box<dyn Error>> {
+        let (buffers, groups) = parse_obj(&fs::read_to_string(path)?)?;
+        let faces: Vec<MeshFace> = groups.into_iter().flat_map(|(_, faces)| faces).collect();
+        Self::new_from(buffers, faces)
+    }
+
+    /// 读取 Wavefront OBJ 文件 `path`，按 `g`/`o` 分组各生成一个网格，返回 `(组名, 网格)` 列表
+    ///
+    /// 各网格共享同一组顶点缓冲；第一条分组语句之前的面归入名为 `default` 的分组，
+    /// 同名分组合并，不含面的分组被略去。错误同 `load_obj`
+    pub fn load_obj_groups(path: &str) -> Result<Vec<(String, Self)>, Box<dyn Error>> {
+        let (buffers, groups) = parse_obj(&fs::read_to_string(path)?)?;
+        let buffers: Arc<MeshBuffers> = Arc::new(buffers);
+        groups
+            .into_iter()
+            .filter(|(_, faces)| !faces.is_empty())
+            .map(|(name, faces)| Ok((name, Self::from_shared(Arc::clone(&buffers), faces)?)))
+            .collect()
+    }
+}
+
+/// 私有函数，解析 OBJ 文本，返回顶点缓冲与按出现顺序排列的 `(组名, 三角面)` 分组
+fn parse_obj(source: &str) -> Result<(MeshBuffers, Vec<ObjGroup>), Box<dyn Error>> {
+    let mut positions: Vec<Coord3> = Vec::new();
+    let mut normals: Vec<Vec3> = Vec::new();
+    let mut uvs: Vec<(f64, f64)> = Vec::new();
+    let mut groups: Vec<ObjGroup> = vec![(DEFAULT_GROUP.to_string(), Vec::new())];
+    let mut current: usize = 0;
+
+    for (idx, line) in source.lines().enumerate() {
+        let line_no: usize = idx + 1;
+        let invalid = || MeshErr::InvalidObjErr(line_no);
+        let line: &str = line.split('#').next().unwrap_or("");
+        let mut tokens = line.split_whitespace();
+        let keyword: &str = match tokens.next() {
+            Some(keyword) => keyword,
+            None => continue,
+        };
+        let args: Vec<&str> = tokens.collect();
+        let floats = |min: usize| -> Result<Vec<f64>, MeshErr> {
+            if args.len() < min {
+                return Err(invalid());
+            }
+            args.iter()
+                .map(|arg| arg.parse::<f64>().ok().filter(|v| v.is_finite()).ok_or_else(invalid))
+                .collect()
+        };
+
+        match keyword {
+            "v" => {
+                let v: Vec<f64> = floats(3)?;
+                positions.push(Coord3::new_from(v[0], v[1], v[2]));
+            }
+            "vn" => {
+                let v: Vec<f64> = floats(3)?;
+                normals.push(Vec3::new_from(v[0], v[1], v[2]));
+            }
+            "vt" => {
+                let v: Vec<f64> = floats(1)?;
+                uvs.push((v[0], v.get(1).copied().unwrap_or(0.0)));
+            }
+            "g" | "o" => {
+                let name: String = if args.is_empty() {
+                    DEFAULT_GROUP.to_string()
+                } else {
+                    args.join(" ")
+                };
+                current = match groups.iter().position(|(group, _)| *group == name) {
+                    Some(i) => i,
+                    None => {
+                        groups.push((name, Vec::new()));
+                        groups.len() - 1
+                    }
+                };
+            }
+            "f" => {
+                if args.len() < 3 {
+                    return Err(Box::new(invalid()));
+                }
+                let vertices: Vec<ObjVertex> = args
+                    .iter()
+                    .map(|arg| ObjVertex::parse(arg, (positions.len(), uvs.len(), normals.len())).ok_or_else(invalid))
+                    .collect::<Result<_, _>>()?;
+                // 以第一个顶点为中心按扇形剖分
+                for i in 1..vertices.len() - 1 {
+                    let corners: [&ObjVertex; 3] = [&vertices[0], &vertices[i], &vertices[i + 1]];
+                    let gather = |pick: fn(&ObjVertex) -> Option<usize>| -> Option<[usize; 3]> {
+                        Some([pick(corners[0])?, pick(corners[1])?, pick(corners[2])?])
+                    };
+                    groups[current].1.push(MeshFace::new_from(
+                        corners.map(|v| v.position),
+                        gather(|v| v.normal),
+                        gather(|v| v.uv),
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Ok((MeshBuffers::new_from(positions, normals, uvs), groups))
+}
+
+/// OBJ 文件中的一个分组：组名与其中的三角面
+type ObjGroup = (String, Vec<MeshFace>);
+
+/// OBJ 面语句中的一个顶点，各索引已转换为 0 索引
+struct ObjVertex {
+    position: usize,
+    uv: Option<usize>,
+    normal: Option<usize>,
+}
+
+impl ObjVertex {
+    /// 解析 `i`、`i/j`、`i//k` 或 `i/j/k` 形式的顶点，`counts` 为当前已读入的顶点坐标、纹理坐标与法向量数
+    ///
+    /// 格式无效或索引为零、越界时返回 `None`
+    fn parse(token: &str, counts: (usize, usize, usize)) -> Option<Self> {
+        let mut parts = token.split('/');
+        let position: usize = Self::resolve(parts.next()?, counts.0)?;
+        let uv: Option<usize> = match parts.next() {
+            None | Some("") => None,
+            Some(part) => Some(Self::resolve(part, counts.1)?),
+        };
+        let normal: Option<usize> = match parts.next() {
+            None | Some("") => None,
+            Some(part) => Some(Self::resolve(part, counts.2)?),
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self { position, uv, normal })
+    }
+
+    /// 私有方法，把 1 索引或负数相对索引（`-1` 为最近读入的一项）转换为 0 索引
+    fn resolve(part: &str, count: usize) -> Option<usize> {
+        let index: i64 = part.parse().ok()?;
+        let resolved: i64 = if index < 0 { count as i64 + index } else { index - 1 };
+        if index == 0 || resolved < 0 || resolved >= count as i64 {
+            return None;
+        }
+        Some(resolved as usize)
+    }
+}
+
+/// 第一条分组语句之前的面所属的分组名
+const DEFAULT_GROUP: &str = "default";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 私有函数，解析 `source`，返回 `MeshErr::InvalidObjErr` 记录的行号
+    fn error_line(source: &str) -> Option<usize> {
+        match parse_obj(source).err()?.downcast_ref::<MeshErr>()? {
+            MeshErr::InvalidObjErr(line) => Some(*line),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn negative_indices_are_relative_to_the_latest_vertex() {
+        let source: &str = "v 0 0 0\nv 1 0 0\nv 0 1 0\nf -3 -2 -1\nv 1 1 0\nf 2 -1 -2\n";
+        let (buffers, groups) = parse_obj(source).unwrap();
+        assert_eq!(buffers.get_positions().len(), 4);
+        let faces: Vec<[usize; 3]> = groups[0].1.iter().map(|face| face.get_positions()).collect();
+        assert_eq!(faces, vec![[0, 1, 2], [1, 3, 2]]);
+    }
+
+    #[test]
+    fn position_normal_vertices_skip_the_uv() {
+        let source: &str = "v 0 0 0\nv 1 0 0\nv 0 1 0\nvn 0 0 1\nf 1//1 2//1 3//-1\n";
+        let (_, groups) = parse_obj(source).unwrap();
+        let face: &MeshFace = &groups[0].1[0];
+        assert_eq!(face.get_normals(), Some([0, 0, 0]));
+        assert_eq!(face.get_uvs(), None);
+    }
+
+    #[test]
+    fn polygons_are_fan_triangulated() {
+        let source: &str = "v 0 0 0\nv 1 0 0\nv 2 1 0\nv 1 2 0\nv 0 1 0\nvt 0 0\nf 1/1 2/1 3/1 4/1 5/1\n";
+        let (_, groups) = parse_obj(source).unwrap();
+        let faces: Vec<[usize; 3]> = groups[0].1.iter().map(|face| face.get_positions()).collect();
+        assert_eq!(faces, vec![[0, 1, 2], [0, 2, 3], [0, 3, 4]]);
+        assert!(groups[0].1.iter().all(|face| face.get_uvs() == Some([0, 0, 0])));
+    }
+
+    #[test]
+    fn faces_are_grouped_by_name() {
+        let source: &str = "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\ng a\nf 1 2 3\no b\nf 1 2 3\ng a\nf 3 2 1\n";
+        let (_, groups) = parse_obj(source).unwrap();
+        let sizes: Vec<(&str, usize)> = groups.iter().map(|(name, faces)| (name.as_str(), faces.len())).collect();
+        assert_eq!(sizes, vec![(DEFAULT_GROUP, 1), ("a", 2), ("b", 1)]);
+    }
+
+    #[test]
+    fn invalid_indices_report_their_line() {
+        let header: &str = "v 0 0 0\nv 1 0 0\nv 0 1 0\n";
+        assert_eq!(error_line(&format!("{header}f 1 2 4\n")), Some(4));
+        assert_eq!(error_line(&format!("{header}f 0 1 2\n")), Some(4));
+        assert_eq!(error_line(&format!("{header}f -4 1 2\n")), Some(4));
+        assert_eq!(error_line(&format!("{header}f 1/1 2/1 3/1\n")), Some(4));
+        assert_eq!(error_line(&format!("{header}f 1//1 2//1 3//1\n")), Some(4));
+        assert_eq!(error_line(&format!("{header}f 1/2/3/4 2 3\n")), Some(4));
+        assert_eq!(error_line(&format!("{header}f 1 2\n")), Some(4));
+    }
+
+    #[test]
+    fn malformed_numbers_are_rejected() {
+        assert_eq!(error_line("v 0 0\n"), Some(1));
+        assert_eq!(error_line("v 0 0 x\n"), Some(1));
+        assert_eq!(error_line("# comment\nv 0 nan 0\n"), Some(2));
+        assert_eq!(error_line("vn inf 0 0\n"), Some(1));
+        assert_eq!(error_line("vt\n"), Some(1));
+    }
+}
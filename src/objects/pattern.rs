@@ -0,0 +1,160 @@
+use std::fmt::Debug;
+
+use crate::basics::color::{Color, WHITE};
+use crate::basics::coord3::Coord3;
+use crate::basics::vec3::Vec3;
+
+/// 随交点位置变化的表面颜色
+pub trait Texture: Debug {
+    /// 返回纹理坐标 `uv`、空间位置 `point` 处的颜色
+    fn value(&self, uv: (f64, f64), point: &Coord3) -> Color;
+}
+
+/// 三维空间棋盘格：以边长为 `scale` 的立方体交替取两种颜色，与物体的纹理坐标无关
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct SpaceChecker {
+    scale: f64,
+    even: Color,
+    odd: Color,
+}
+
+impl SpaceChecker {
+    pub fn new_from(scale: f64, even: Color, odd: Color) -> Self {
+        Self { scale, even, odd }
+    }
+}
+
+impl Texture for SpaceChecker {
+    fn value(&self, _uv: (f64, f64), point: &Coord3) -> Color {
+        let cell = |value: f64| -> i64 { (value / self.scale).floor() as i64 };
+        if (cell(point.x()) + cell(point.y()) + cell(point.z())).rem_euclid(2) == 0 {
+            self.even
+        } else {
+            self.odd
+        }
+    }
+}
+
+/// 纹理坐标棋盘格：将 `[0, 1] * [0, 1]` 的纹理坐标划分为 `u_count * v_count` 格交替取两种颜色
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct UvChecker {
+    u_count: usize,
+    v_count: usize,
+    even: Color,
+    odd: Color,
+}
+
+impl UvChecker {
+    pub fn new_from(u_count: usize, v_count: usize, even: Color, odd: Color) -> Self {
+        Self {
+            u_count,
+            v_count,
+            even,
+            odd,
+        }
+    }
+}
+
+impl Texture for UvChecker {
+    fn value(&self, uv: (f64, f64), _point: &Coord3) -> Color {
+        let u_cell: i64 = (uv.0 * self.u_count as f64).floor() as i64;
+        let v_cell: i64 = (uv.1 * self.v_count as f64).floor() as i64;
+        if (u_cell + v_cell).rem_euclid(2) == 0 {
+            self.even
+        } else {
+            self.odd
+        }
+    }
+}
+
+/// 线性渐变：从 `origin` 沿 `span` 方向由 `start` 过渡到 `end`，超出两端的部分取端点颜色
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct LinearGradient {
+    origin: Coord3,
+    /// 渐变的方向与长度
+    span: Vec3,
+    start: Color,
+    end: Color,
+}
+
+impl LinearGradient {
+    pub fn new_from(origin: Coord3, span: Vec3, start: Color, end: Color) -> Self {
+        Self {
+            origin,
+            span,
+            start,
+            end,
+        }
+    }
+}
+
+impl Texture for LinearGradient {
+    fn value(&self, _uv: (f64, f64), point: &Coord3) -> Color {
+        let t: f64 = ((point - self.origin) * self.span / (self.span * self.span)).clamp(0.0, 1.0);
+        lerp(self.start, self.end, t)
+    }
+}
+
+/// 径向渐变：以 `center` 为球心，由 `inner` 过渡到距离 `radius` 处的 `outer`，更远处取 `outer`
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct RadialGradient {
+    center: Coord3,
+    radius: f64,
+    inner: Color,
+    outer: Color,
+}
+
+impl RadialGradient {
+    pub fn new_from(center: Coord3, radius: f64, inner: Color, outer: Color) -> Self {
+        Self {
+            center,
+            radius,
+            inner,
+            outer,
+        }
+    }
+}
+
+impl Texture for RadialGradient {
+    fn value(&self, _uv: (f64, f64), point: &Coord3) -> Color {
+        let t: f64 = (point.distance_to(&self.center) / self.radius).clamp(0.0, 1.0);
+        lerp(self.inner, self.outer, t)
+    }
+}
+
+/// 纹理坐标调试网格：底色的红、绿分量分别等于 `u`、`v`，
+/// 并在每 `1 / divisions` 处画出宽度为 `line_width`（纹理坐标单位）的白线
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct UvDebugGrid {
+    divisions: usize,
+    line_width: f64,
+}
+
+impl UvDebugGrid {
+    pub fn new_from(divisions: usize, line_width: f64) -> Self {
+        Self {
+            divisions,
+            line_width,
+        }
+    }
+}
+
+impl Texture for UvDebugGrid {
+    fn value(&self, uv: (f64, f64), _point: &Coord3) -> Color {
+        let near_line = |value: f64| -> bool {
+            let scaled: f64 = value * self.divisions as f64;
+            let dist: f64 = (scaled - scaled.round()).abs() / self.divisions as f64;
+            dist < 0.5 * self.line_width
+        };
+        if near_line(uv.0) || near_line(uv.1) {
+            WHITE
+        } else {
+            Color::new_from(uv.0.clamp(0.0, 1.0), uv.1.clamp(0.0, 1.0), 0.0)
+        }
+    }
+}
+
+/// 私有函数，颜色的线性插值
+fn lerp(from: Color, to: Color, t: f64) -> Color {
+    (1.0 - t) * from + t * to
+}
@@ -0,0 +1,174 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::basics::coord3::Coord3;
+use crate::basics::vec3::{Vec3, ZERO_VEC3};
+use crate::errors::{MainErr, nan};
+use crate::objects::alignedbox::AlignedBox;
+use crate::objects::texture::OpaqueTexture;
+use crate::objects::{Bounded, Object};
+use crate::rays::hit::HitRecord;
+use crate::rays::ray::{Ray, RayIntersectOpaque, t_in_bound};
+
+/// 过点 `point`、法向量为 `normal` 的无限大平面
+///
+/// 包围盒在平面延伸的方向上为无穷大，因此不应放入 `Bvh`，而应直接放入场景
+#[derive(Debug, PartialEq, Clone)]
+pub struct OpaquePlane {
+    point: Coord3,
+    /// 朝向平面外侧的单位法向量
+    normal: Vec3,
+    texture: Option<OpaqueTexture>,
+}
+
+impl OpaquePlane {
+    /// 参数含 `f64::NAN` 时返回 `MainErr`，`normal` 为零向量时返回 `PlaneErr::InvalidParamErr`
+    pub fn new_from(point: Coord3, normal: Vec3) -> Result<Self, Box<dyn Error>> {
+        for value in [point.x(), point.y(), point.z(), normal.x(), normal.y(), normal.z()] {
+            nan::check::<MainErr>(value, "OpaquePlane::new_from")?;
+        }
+        if normal == ZERO_VEC3 {
+            return Err(Box::new(PlaneErr::InvalidParamErr));
+        }
+        Ok(Self {
+            point,
+            normal: normal.normalize(),
+            texture: None,
+        })
+    }
+
+    pub fn get_point(&self) -> &Coord3 {
+        &self.point
+    }
+
+    pub fn get_normal(&self) -> &Vec3 {
+        &self.normal
+    }
+
+    pub fn get_texture(&self) -> Option<&OpaqueTexture> {
+        self.texture.as_ref()
+    }
+
+    pub fn set_texture(&mut self, texture: OpaqueTexture) -> &mut Self {
+        self.texture = Some(texture);
+        self
+    }
+}
+
+impl RayIntersectOpaque for OpaquePlane {
+    /// 纹理坐标为交点在平面内一组正交基下的坐标，按单位长度重复
+    fn intersection(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<Option<HitRecord<'_>>, Box<dyn Error>> {
+        let denom: f64 = ray.get_direction() * self.normal;
+        if denom.abs() < PARALLEL_EPSILON {
+            return Ok(None);
+        }
+        let t: f64 = (self.point - ray.get_origin()) * self.normal / denom;
+        if !t_in_bound(t, t_bound) {
+            return Ok(None);
+        }
+        let (u_axis, v_axis) = self.normal.orthonormal_basis();
+        let offset: Vec3 = ray.at(t) - self.point;
+        let uv: (f64, f64) = ((offset * u_axis).rem_euclid(1.0), (offset * v_axis).rem_euclid(1.0));
+        Ok(Some(HitRecord::new_from(ray, t, self.normal, uv, self.texture.as_ref())))
+    }
+}
+
+impl Bounded for OpaquePlane {
+    /// 法向量与某坐标轴平行时，包围盒在该轴上为平面所在位置（略微扩展），其余各轴均为无穷大
+    fn bounding_box(&self) -> AlignedBox {
+        let bound = |normal: f64, value: f64| -> (f64, f64) {
+            if (normal.abs() - 1.0).abs() < PARALLEL_EPSILON {
+                (value - BOUND_PADDING, value + BOUND_PADDING)
+            } else {
+                (f64::NEG_INFINITY, f64::INFINITY)
+            }
+        };
+        let (x0, x1) = bound(self.normal.x(), self.point.x());
+        let (y0, y1) = bound(self.normal.y(), self.point.y());
+        let (z0, z1) = bound(self.normal.z(), self.point.z());
+        AlignedBox::from_corners(&Coord3::new_from(x0, y0, z0), &Coord3::new_from(x1, y1, z1))
+    }
+}
+
+impl Object for OpaquePlane {}
+
+#[derive(Debug, Clone, Copy)]
+pub enum PlaneErr {
+    /// 输入了无效的初始化参数
+    InvalidParamErr,
+}
+
+impl Display for PlaneErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidParamErr => write!(f, "invalid plane construction"),
+        }
+    }
+}
+
+impl Error for PlaneErr {}
+
+impl PlaneErr {
+    pub fn handle(&self) {
+        eprintln!("[Plane Error] {}", self);
+    }
+}
+
+/// 光线方向与平面法向量的点积绝对值小于该值时视为平行
+const PARALLEL_EPSILON: f64 = 1e-12;
+const BOUND_PADDING: f64 = 1e-6;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rays::ray::FORWARD_T_BOUND;
+
+    /// 私有函数，过点 `(0, 0, 1)`、法向量沿 z 轴的平面
+    fn plane() -> OpaquePlane {
+        OpaquePlane::new_from(Coord3::new_from(0.0, 0.0, 1.0), Vec3::new_from(0.0, 0.0, 2.0)).unwrap()
+    }
+
+    fn assert_close(v1: &Vec3, v2: &Vec3) {
+        assert!((v1 - v2).magnitude() < 1e-9, "{:?} != {:?}", v1, v2);
+    }
+
+    #[test]
+    fn plane_is_hit_from_both_sides() {
+        let plane: OpaquePlane = plane();
+        let down = Ray::new_from(Coord3::new_from(3.25, -7.5, 4.0), Vec3::new_from(0.0, 0.0, -1.0));
+        let hit = plane.intersection(&down, FORWARD_T_BOUND).unwrap().unwrap();
+        assert!((hit.get_t() - 3.0).abs() < 1e-9 && hit.is_front_face());
+        assert_close(hit.get_normal(), &Vec3::new_from(0.0, 0.0, 1.0));
+        let (u, v) = hit.get_uv();
+        assert!((0.0..1.0).contains(&u) && (0.0..1.0).contains(&v));
+
+        let up = Ray::new_from(Coord3::new_from(0.5, 0.0, -1.0), Vec3::new_from(0.0, 0.0, 1.0));
+        let hit = plane.intersection(&up, FORWARD_T_BOUND).unwrap().unwrap();
+        assert!((hit.get_t() - 2.0).abs() < 1e-9 && !hit.is_front_face());
+        assert_close(hit.get_normal(), &Vec3::new_from(0.0, 0.0, -1.0));
+
+        assert!(plane.intersection(&down, (0.0, 2.0)).unwrap().is_none());
+        let away = Ray::new_from(Coord3::new_from(0.0, 0.0, 4.0), Vec3::new_from(0.0, 0.0, 1.0));
+        assert!(plane.intersection(&away, FORWARD_T_BOUND).unwrap().is_none());
+        let parallel = Ray::new_from(Coord3::new_from(0.0, 0.0, 4.0), Vec3::new_from(1.0, 1.0, 0.0));
+        assert!(plane.intersection(&parallel, FORWARD_T_BOUND).unwrap().is_none());
+    }
+
+    #[test]
+    fn bounding_box_is_thin_only_along_an_axis_normal() {
+        let bound: AlignedBox = plane().bounding_box();
+        let (z0, z1) = bound.get_z();
+        assert!(z0 < 1.0 && 1.0 < z1 && z1 - z0 < 1e-3);
+        assert_eq!(bound.get_x(), (f64::NEG_INFINITY, f64::INFINITY));
+        assert!(!bound.is_bounded());
+
+        let tilted = OpaquePlane::new_from(Coord3::new(), Vec3::new_from(1.0, 0.0, 1.0)).unwrap();
+        assert_eq!(tilted.bounding_box().get_z(), (f64::NEG_INFINITY, f64::INFINITY));
+    }
+
+    #[test]
+    fn zero_normal_is_rejected() {
+        assert!(OpaquePlane::new_from(Coord3::new(), ZERO_VEC3).is_err());
+        assert!(OpaquePlane::new_from(Coord3::new_from(f64::NAN, 0.0, 0.0), Vec3::new_from(0.0, 0.0, 1.0)).is_err());
+    }
+}
@@ -0,0 +1,488 @@
+use std::error::Error;
+use std::fs;
+
+use crate::basics::color::Color;
+use crate::basics::coord3::Coord3;
+use crate::basics::vec3::Vec3;
+use crate::objects::mesh::{MeshBuffers, MeshErr, MeshFace, TriangleMesh};
+
+impl TriangleMesh {
+    /// 读取 PLY 文件 `path`，支持 `ascii`、`binary_little_endian` 与 `binary_big_endian` 三种编码
+    ///
+    /// `vertex` 元素须含 `x`、`y`、`z` 属性，可选 `nx`、`ny`、`nz` 法向量，
+    /// `red`、`green`、`blue` 颜色（作为顶点颜色与材质颜色相乘）与 `u`、`v`（或 `s`、`t`、`texture_u`、`texture_v`）纹理坐标；
+    /// `face` 元素的 `vertex_indices`（或 `vertex_index`）列表按扇形剖分为三角面，其余元素与属性被忽略
+    ///
+    /// 文件格式无效时返回 `MeshErr::InvalidPlyErr`，其余错误同 `TriangleMesh::new_from`
+    pub fn load_ply(path: &str) -> Result<Self, Box<dyn Error>> {
+        let (buffers, faces) = parse_ply(&fs::read(path)?)?;
+        Self::new_from(buffers, faces)
+    }
+}
+
+/// 私有函数，解析 PLY 文件内容，返回顶点缓冲与三角面
+fn parse_ply(bytes: &[u8]) -> Result<(MeshBuffers, Vec<MeshFace>), Box<dyn Error>> {
+    let (header, body_start) = PlyHeader::parse(bytes).ok_or(MeshErr::InvalidPlyErr)?;
+    let mut reader = PlyReader {
+        bytes,
+        pos: body_start,
+        format: header.format,
+    };
+
+    let mut positions: Vec<Coord3> = Vec::new();
+    let mut normals: Vec<Vec3> = Vec::new();
+    let mut uvs: Vec<(f64, f64)> = Vec::new();
+    let mut colors: Vec<Color> = Vec::new();
+    let mut triangles: Vec<[usize; 3]> = Vec::new();
+
+    for element in &header.elements {
+        match element.name.as_str() {
+            "vertex" => {
+                let layout: VertexLayout = VertexLayout::of(element).ok_or(MeshErr::InvalidPlyErr)?;
+                for _ in 0..element.count {
+                    let values: Vec<f64> = element
+                        .properties
+                        .iter()
+                        .map(|property| reader.property(&property.kind).map(|v| v.first().copied().unwrap_or(0.0)))
+                        .collect::<Option<_>>()
+                        .ok_or(MeshErr::InvalidPlyErr)?;
+                    let [x, y, z] = layout.position.map(|i| values[i]);
+                    positions.push(Coord3::new_from(x, y, z));
+                    if let Some(indices) = layout.normal {
+                        let [x, y, z] = indices.map(|i| values[i]);
+                        normals.push(Vec3::new_from(x, y, z));
+                    }
+                    if let Some([u, v]) = layout.uv {
+                        uvs.push((values[u], values[v]));
+                    }
+                    if let Some(indices) = layout.color {
+                        let [r, g, b] = indices.map(|(i, scale)| values[i] * scale);
+                        colors.push(Color::new_from(r, g, b));
+                    }
+                }
+            }
+            "face" => {
+                for _ in 0..element.count {
+                    let mut polygon: Option<Vec<usize>> = None;
+                    for property in &element.properties {
+                        let values: Vec<f64> = reader.property(&property.kind).ok_or(MeshErr::InvalidPlyErr)?;
+                        if matches!(property.name.as_str(), "vertex_indices" | "vertex_index") {
+                            let indices: Option<Vec<usize>> = values
+                                .iter()
+                                .map(|&i| (i >= 0.0 && i.fract() == 0.0).then_some(i as usize))
+                                .collect();
+                            polygon = Some(indices.ok_or(MeshErr::InvalidPlyErr)?);
+                        }
+                    }
+                    let polygon: Vec<usize> = polygon.ok_or(MeshErr::InvalidPlyErr)?;
+                    // 以第一个顶点为中心按扇形剖分，顶点数不足 3 的面被忽略
+                    for i in 1..polygon.len().saturating_sub(1) {
+                        triangles.push([polygon[0], polygon[i], polygon[i + 1]]);
+                    }
+                }
+            }
+            _ => {
+                for _ in 0..element.count {
+                    for property in &element.properties {
+                        reader.property(&property.kind).ok_or(MeshErr::InvalidPlyErr)?;
+                    }
+                }
+            }
+        }
+    }
+
+    // 各顶点属性与顶点坐标一一对应，三角面对所有缓冲使用同一组索引
+    let (has_normals, has_uvs) = (!normals.is_empty(), !uvs.is_empty());
+    let faces: Vec<MeshFace> = triangles
+        .into_iter()
+        .map(|indices| MeshFace::new_from(indices, has_normals.then_some(indices), has_uvs.then_some(indices)))
+        .collect();
+    let mut buffers: MeshBuffers = MeshBuffers::new_from(positions, normals, uvs);
+    buffers.set_colors(colors);
+    Ok((buffers, faces))
+}
+
+/// PLY 文件的编码方式
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum PlyFormat {
+    Ascii,
+    BinaryLittleEndian,
+    BinaryBigEndian,
+}
+
+/// PLY 属性的标量类型
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum PlyType {
+    Int8,
+    UInt8,
+    Int16,
+    UInt16,
+    Int32,
+    UInt32,
+    Float32,
+    Float64,
+}
+
+impl PlyType {
+    /// 私有方法，由类型名解析，同时接受 `uchar` 与 `uint8` 两套命名
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "char" | "int8" => Some(Self::Int8),
+            "uchar" | "uint8" => Some(Self::UInt8),
+            "short" | "int16" => Some(Self::Int16),
+            "ushort" | "uint16" => Some(Self::UInt16),
+            "int" | "int32" => Some(Self::Int32),
+            "uint" | "uint32" => Some(Self::UInt32),
+            "float" | "float32" => Some(Self::Float32),
+            "double" | "float64" => Some(Self::Float64),
+            _ => None,
+        }
+    }
+
+    /// 私有方法，二进制编码下所占字节数
+    fn size(&self) -> usize {
+        match self {
+            Self::Int8 | Self::UInt8 => 1,
+            Self::Int16 | Self::UInt16 => 2,
+            Self::Int32 | Self::UInt32 | Self::Float32 => 4,
+            Self::Float64 => 8,
+        }
+    }
+
+    /// 私有方法，颜色分量换算到 `[0, 1]` 所乘的系数：整数类型除以其最大值，浮点类型保持不变
+    fn color_scale(&self) -> f64 {
+        match self {
+            Self::Int8 => 1.0 / i8::MAX as f64,
+            Self::UInt8 => 1.0 / u8::MAX as f64,
+            Self::Int16 => 1.0 / i16::MAX as f64,
+            Self::UInt16 => 1.0 / u16::MAX as f64,
+            Self::Int32 => 1.0 / i32::MAX as f64,
+            Self::UInt32 => 1.0 / u32::MAX as f64,
+            Self::Float32 | Self::Float64 => 1.0,
+        }
+    }
+}
+
+/// PLY 属性的种类：标量，或由计数类型与元素类型描述的列表
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum PlyKind {
+    Scalar(PlyType),
+    List(PlyType, PlyType),
+}
+
+#[derive(Debug, Clone)]
+struct PlyProperty {
+    name: String,
+    kind: PlyKind,
+}
+
+#[derive(Debug, Clone)]
+struct PlyElement {
+    name: String,
+    count: usize,
+    properties: Vec<PlyProperty>,
+}
+
+#[derive(Debug, Clone)]
+struct PlyHeader {
+    format: PlyFormat,
+    elements: Vec<PlyElement>,
+}
+
+impl PlyHeader {
+    /// 私有方法，解析文件头，返回文件头与数据部分的起始位置
+    fn parse(bytes: &[u8]) -> Option<(Self, usize)> {
+        let mut format: Option<PlyFormat> = None;
+        let mut elements: Vec<PlyElement> = Vec::new();
+        let mut pos: usize = 0;
+        let mut first: bool = true;
+
+        loop {
+            let end: usize = pos + bytes[pos..].iter().position(|&b| b == b'\n')?;
+            let line: &str = std::str::from_utf8(&bytes[pos..end]).ok()?.trim_end_matches('\r');
+            pos = end + 1;
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+
+            if first {
+                if tokens != ["ply"] {
+                    return None;
+                }
+                first = false;
+                continue;
+            }
+            match tokens.as_slice() {
+                ["format", encoding, _version] => {
+                    format = Some(match *encoding {
+                        "ascii" => PlyFormat::Ascii,
+                        "binary_little_endian" => PlyFormat::BinaryLittleEndian,
+                        "binary_big_endian" => PlyFormat::BinaryBigEndian,
+                        _ => return None,
+                    });
+                }
+                ["element", name, count] => elements.push(PlyElement {
+                    name: name.to_string(),
+                    count: count.parse().ok()?,
+                    properties: Vec::new(),
+                }),
+                ["property", "list", count_type, item_type, name] => {
+                    elements.last_mut()?.properties.push(PlyProperty {
+                        name: name.to_string(),
+                        kind: PlyKind::List(PlyType::parse(count_type)?, PlyType::parse(item_type)?),
+                    });
+                }
+                ["property", value_type, name] => {
+                    elements.last_mut()?.properties.push(PlyProperty {
+                        name: name.to_string(),
+                        kind: PlyKind::Scalar(PlyType::parse(value_type)?),
+                    });
+                }
+                ["end_header"] => break,
+                ["comment", ..] | ["obj_info", ..] | [] => {}
+                _ => return None,
+            }
+        }
+
+        Some((
+            Self {
+                format: format?,
+                elements,
+            },
+            pos,
+        ))
+    }
+}
+
+/// `vertex` 元素中各所需属性在属性列表中的位置
+struct VertexLayout {
+    position: [usize; 3],
+    normal: Option<[usize; 3]>,
+    uv: Option<[usize; 2]>,
+    /// 各颜色分量的位置及其换算到 `[0, 1]` 的系数
+    color: Option<[(usize, f64); 3]>,
+}
+
+impl VertexLayout {
+    /// 私有方法，缺少顶点坐标属性时返回 `None`
+    fn of(element: &PlyElement) -> Option<Self> {
+        let find = |name: &str| -> Option<usize> {
+            element
+                .properties
+                .iter()
+                .position(|property| property.name == name && matches!(property.kind, PlyKind::Scalar(_)))
+        };
+        let find_all = |names: [&str; 3]| -> Option<[usize; 3]> { Some([find(names[0])?, find(names[1])?, find(names[2])?]) };
+        let color_scale = |i: usize| -> f64 {
+            match element.properties[i].kind {
+                PlyKind::Scalar(value_type) => value_type.color_scale(),
+                PlyKind::List(..) => 1.0,
+            }
+        };
+
+        let uv: Option<[usize; 2]> = UV_NAMES
+            .iter()
+            .find_map(|[u, v]| Some([find(u)?, find(v)?]));
+        let color: Option<[(usize, f64); 3]> = COLOR_NAMES
+            .iter()
+            .find_map(|&names| find_all(names))
+            .map(|indices| indices.map(|i| (i, color_scale(i))));
+        Some(Self {
+            position: find_all(["x", "y", "z"])?,
+            normal: find_all(["nx", "ny", "nz"]),
+            uv,
+            color,
+        })
+    }
+}
+
+/// 按编码方式逐个读取属性值
+struct PlyReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    format: PlyFormat,
+}
+
+impl PlyReader<'_> {
+    /// 私有方法，读取一个属性，标量属性返回单元素列表；数据不足或格式无效时返回 `None`
+    fn property(&mut self, kind: &PlyKind) -> Option<Vec<f64>> {
+        match kind {
+            PlyKind::Scalar(value_type) => Some(vec![self.value(*value_type)?]),
+            PlyKind::List(count_type, item_type) => {
+                let count: f64 = self.value(*count_type)?;
+                if count < 0.0 || count.fract() != 0.0 {
+                    return None;
+                }
+                (0..count as usize).map(|_| self.value(*item_type)).collect()
+            }
+        }
+    }
+
+    /// 私有方法，读取一个标量值
+    fn value(&mut self, value_type: PlyType) -> Option<f64> {
+        match self.format {
+            PlyFormat::Ascii => self.ascii_value(),
+            PlyFormat::BinaryLittleEndian | PlyFormat::BinaryBigEndian => self.binary_value(value_type),
+        }
+    }
+
+    /// 私有方法，读取下一个以空白分隔的文本数值
+    ///
+    /// `nan`、`inf` 等非有限值视为格式无效
+    fn ascii_value(&mut self) -> Option<f64> {
+        while self.bytes.get(self.pos)?.is_ascii_whitespace() {
+            self.pos += 1;
+        }
+        let start: usize = self.pos;
+        while self.bytes.get(self.pos).is_some_and(|b| !b.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+        let value: f64 = std::str::from_utf8(&self.bytes[start..self.pos]).ok()?.parse().ok()?;
+        value.is_finite().then_some(value)
+    }
+
+    /// 私有方法，按字节序读取一个二进制数值
+    fn binary_value(&mut self, value_type: PlyType) -> Option<f64> {
+        let size: usize = value_type.size();
+        let mut raw: [u8; 8] = [0; 8];
+        raw[..size].copy_from_slice(self.bytes.get(self.pos..self.pos + size)?);
+        self.pos += size;
+        if self.format == PlyFormat::BinaryBigEndian {
+            raw[..size].reverse();
+        }
+        let value: f64 = match value_type {
+            PlyType::Int8 => i8::from_le_bytes([raw[0]]) as f64,
+            PlyType::UInt8 => raw[0] as f64,
+            PlyType::Int16 => i16::from_le_bytes([raw[0], raw[1]]) as f64,
+            PlyType::UInt16 => u16::from_le_bytes([raw[0], raw[1]]) as f64,
+            PlyType::Int32 => i32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]) as f64,
+            PlyType::UInt32 => u32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]) as f64,
+            PlyType::Float32 => f32::from_le_bytes([raw[0], raw[1], raw[2], raw[3]]) as f64,
+            PlyType::Float64 => f64::from_le_bytes(raw),
+        };
+        value.is_finite().then_some(value)
+    }
+}
+
+/// 纹理坐标属性的常见命名，按优先顺序排列
+const UV_NAMES: [[&str; 2]; 4] = [["u", "v"], ["s", "t"], ["texture_u", "texture_v"], ["texture_s", "texture_t"]];
+/// 颜色属性的常见命名，按优先顺序排列
+const COLOR_NAMES: [[&str; 3]; 2] = [["red", "green", "blue"], ["r", "g", "b"]];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 测试网格的顶点：坐标、纹理坐标与颜色
+    const VERTICES: [([f32; 3], [f64; 2], [u8; 3]); 4] = [
+        ([0.0, 0.0, 0.0], [0.0, 0.0], [255, 0, 0]),
+        ([1.0, 0.0, 0.0], [1.0, 0.0], [0, 255, 0]),
+        ([1.0, 1.0, 0.5], [1.0, 1.0], [0, 0, 255]),
+        ([0.0, 1.0, -0.25], [0.0, 1.0], [51, 102, 153]),
+    ];
+    /// 测试网格的面：一个四边形与一个三角形
+    const FACES: [&[i32]; 2] = [&[0, 1, 2, 3], &[0, 2, 1]];
+
+    /// 私有函数，以编码 `format` 生成测试网格的 PLY 文件头
+    fn header(format: &str) -> String {
+        format!(
+            "ply\nformat {format} 1.0\ncomment test mesh\nelement vertex 4\n\
+             property float x\nproperty float y\nproperty float z\n\
+             property double u\nproperty double v\n\
+             property uchar red\nproperty uchar green\nproperty uchar blue\n\
+             element face 2\nproperty list uchar int vertex_indices\nend_header\n"
+        )
+    }
+
+    /// 私有函数，以 `ascii` 编码生成测试网格
+    fn ascii() -> Vec<u8> {
+        let mut source: String = header("ascii");
+        for ([x, y, z], [u, v], [r, g, b]) in VERTICES {
+            source += &format!("{x} {y} {z} {u} {v} {r} {g} {b}\n");
+        }
+        for face in FACES {
+            let indices: Vec<String> = face.iter().map(|i| i.to_string()).collect();
+            source += &format!("{} {}\n", face.len(), indices.join(" "));
+        }
+        source.into_bytes()
+    }
+
+    /// 私有函数，以二进制编码生成测试网格，`big_endian` 决定字节序
+    fn binary(big_endian: bool) -> Vec<u8> {
+        let format: &str = if big_endian { "binary_big_endian" } else { "binary_little_endian" };
+        let mut bytes: Vec<u8> = header(format).into_bytes();
+        let mut push = |mut raw: Vec<u8>| {
+            if big_endian {
+                raw.reverse();
+            }
+            bytes.extend(raw);
+        };
+        for (position, uv, color) in VERTICES {
+            position.iter().for_each(|value| push(value.to_le_bytes().to_vec()));
+            uv.iter().for_each(|value| push(value.to_le_bytes().to_vec()));
+            color.iter().for_each(|value| push(vec![*value]));
+        }
+        for face in FACES {
+            push(vec![face.len() as u8]);
+            face.iter().for_each(|index| push(index.to_le_bytes().to_vec()));
+        }
+        bytes
+    }
+
+    #[test]
+    fn encodings_agree() {
+        let (buffers, faces) = parse_ply(&ascii()).unwrap();
+        assert_eq!(buffers.get_positions()[3], Coord3::new_from(0.0, 1.0, -0.25));
+        assert_eq!(buffers.get_uvs()[2], (1.0, 1.0));
+        assert_eq!(buffers.get_colors()[3], Color::new_from(0.2, 0.4, 0.6));
+        assert!(buffers.get_normals().is_empty());
+        let indices: Vec<[usize; 3]> = faces.iter().map(|face| face.get_positions()).collect();
+        assert_eq!(indices, vec![[0, 1, 2], [0, 2, 3], [0, 2, 1]]);
+        assert!(faces.iter().all(|face| face.get_uvs() == Some(face.get_positions()) && face.get_normals().is_none()));
+
+        for big_endian in [false, true] {
+            let (binary_buffers, binary_faces) = parse_ply(&binary(big_endian)).unwrap();
+            assert_eq!(binary_buffers, buffers);
+            assert_eq!(binary_faces, faces);
+        }
+        assert!(TriangleMesh::new_from(buffers, faces).is_ok());
+    }
+
+    #[test]
+    fn malformed_headers_are_rejected() {
+        let valid: String = header("ascii");
+        let broken: [String; 7] = [
+            valid.replacen("ply\n", "plx\n", 1),
+            valid.replacen("ascii", "binary_middle_endian", 1),
+            valid.replacen("format ascii 1.0\n", "", 1),
+            valid.replacen("float x", "quad x", 1),
+            valid.replacen("element vertex 4", "element vertex -4", 1),
+            valid.replacen("end_header\n", "", 1),
+            valid.replacen("property float x\n", "", 1),
+        ];
+        for source in broken {
+            let mut bytes: Vec<u8> = source.into_bytes();
+            bytes.extend_from_slice(&ascii()[valid.len()..]);
+            assert!(parse_ply(&bytes).is_err());
+        }
+        assert!(parse_ply(b"").is_err());
+        assert!(parse_ply(b"ply").is_err());
+    }
+
+    #[test]
+    fn truncated_or_invalid_bodies_are_rejected() {
+        let bytes: Vec<u8> = binary(false);
+        assert!(parse_ply(&bytes[..bytes.len() - 1]).is_err());
+
+        let source: String = String::from_utf8(ascii()).unwrap();
+        for (from, to) in [("0 1 -0.25", "0 1 nan"), ("0 1 -0.25", "0 1 inf"), ("3 0 2 1", "3 0 -2 1"), ("3 0 2 1", "3 0 2.5 1")] {
+            assert!(parse_ply(source.replacen(from, to, 1).as_bytes()).is_err());
+        }
+    }
+
+    #[test]
+    fn out_of_range_indices_are_rejected() {
+        let source: String = String::from_utf8(ascii()).unwrap().replacen("3 0 2 1", "3 0 2 4", 1);
+        let (buffers, faces) = parse_ply(source.as_bytes()).unwrap();
+        let err: Box<dyn Error> = TriangleMesh::new_from(buffers, faces).err().unwrap();
+        assert!(matches!(err.downcast_ref::<MeshErr>(), Some(MeshErr::InvalidIndexErr)));
+    }
+}
@@ -0,0 +1,218 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::basics::coord3::Coord3;
+use crate::basics::random;
+use crate::basics::vec3::{Vec3, ZERO_VEC3};
+use crate::errors::{MainErr, nan};
+use crate::objects::alignedbox::AlignedBox;
+use crate::objects::emitter::{Emitter, EmitterSample, area_to_solid_angle};
+use crate::objects::texture::OpaqueTexture;
+use crate::objects::triangle::triangle_bounding_box;
+use crate::objects::{Bounded, Object};
+use crate::rays::hit::HitRecord;
+use crate::rays::ray::{FORWARD_T_BOUND, Ray, RayIntersectOpaque, t_in_bound};
+
+/// 由顶点 `corner` 与两条邻边 `u`、`v` 张成的平行四边形，四个顶点为 `corner`、`corner + u`、`corner + u + v`、`corner + v`
+#[derive(Debug, PartialEq, Clone)]
+pub struct OpaqueQuad {
+    corner: Coord3,
+    u: Vec3,
+    v: Vec3,
+    /// `u × v` 方向的单位法向量
+    normal: Vec3,
+    texture: Option<OpaqueTexture>,
+}
+
+impl OpaqueQuad {
+    /// 参数含 `f64::NAN` 时返回 `MainErr`，两邻边共线或有零向量时返回 `QuadErr::InvalidParamErr`
+    pub fn new_from(corner: Coord3, u: Vec3, v: Vec3) -> Result<Self, Box<dyn Error>> {
+        for value in [corner.x(), corner.y(), corner.z(), u.x(), u.y(), u.z(), v.x(), v.y(), v.z()] {
+            nan::check::<MainErr>(value, "OpaqueQuad::new_from")?;
+        }
+        let n: Vec3 = u.cross(&v);
+        if n == ZERO_VEC3 {
+            return Err(Box::new(QuadErr::InvalidParamErr));
+        }
+        Ok(Self {
+            corner,
+            u,
+            v,
+            normal: n.normalize(),
+            texture: None,
+        })
+    }
+
+    pub fn get_corner(&self) -> &Coord3 {
+        &self.corner
+    }
+
+    /// 两条邻边 `(u, v)`
+    pub fn get_edges(&self) -> (&Vec3, &Vec3) {
+        (&self.u, &self.v)
+    }
+
+    pub fn get_texture(&self) -> Option<&OpaqueTexture> {
+        self.texture.as_ref()
+    }
+
+    pub fn set_texture(&mut self, texture: OpaqueTexture) -> &mut Self {
+        self.texture = Some(texture);
+        self
+    }
+
+    /// 平行四边形的面积
+    pub fn area(&self) -> f64 {
+        self.u.cross(&self.v).magnitude()
+    }
+}
+
+impl RayIntersectOpaque for OpaqueQuad {
+    /// 交点记录的法向量为 `u × v` 方向，纹理坐标为交点沿 `u`、`v` 的比例 `(α, β)`
+    fn intersection(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<Option<HitRecord<'_>>, Box<dyn Error>> {
+        let denom: f64 = ray.get_direction() * self.normal;
+        if denom.abs() < PARALLEL_EPSILON {
+            return Ok(None);
+        }
+        let t: f64 = (self.corner - ray.get_origin()) * self.normal / denom;
+        if !t_in_bound(t, t_bound) {
+            return Ok(None);
+        }
+        // 交点相对 corner 的偏移 p = α·u + β·v，两侧同与 v、u 叉乘后再投影到 n 上即可解出 α、β
+        let n: Vec3 = self.u.cross(&self.v);
+        let w: Vec3 = (1.0 / (n * n)) * n;
+        let p: Vec3 = ray.at(t) - self.corner;
+        let alpha: f64 = w * p.cross(&self.v);
+        let beta: f64 = w * self.u.cross(&p);
+        if !(0.0..=1.0).contains(&alpha) || !(0.0..=1.0).contains(&beta) {
+            return Ok(None);
+        }
+        let mut hit = HitRecord::new_from(ray, t, self.normal, (alpha, beta), self.texture.as_ref());
+        hit.set_emitter(self.as_emitter());
+        Ok(Some(hit))
+    }
+}
+
+impl Object for OpaqueQuad {
+    fn as_emitter(&self) -> Option<&dyn Emitter> {
+        match &self.texture {
+            Some(texture) if texture.is_emissive() => Some(self),
+            _ => None,
+        }
+    }
+}
+
+impl Emitter for OpaqueQuad {
+    /// 在平行四边形上按面积均匀采样
+    fn sample_emitter(&self, origin: &Coord3) -> Option<EmitterSample> {
+        let texture: &OpaqueTexture = self.texture.as_ref()?;
+        let point: Coord3 = self.corner + random::random_f64() * self.u + random::random_f64() * self.v;
+        let pdf: f64 = area_to_solid_angle(1.0 / self.area(), origin, &point, &self.normal);
+        if pdf <= 0.0 {
+            return None;
+        }
+        Some(EmitterSample::new_from(point, pdf, texture.emitted()))
+    }
+
+    fn emitter_pdf(&self, origin: &Coord3, direction: &Vec3) -> f64 {
+        match self.intersection(&Ray::new_from(*origin, *direction), FORWARD_T_BOUND) {
+            Ok(Some(hit)) => area_to_solid_angle(1.0 / self.area(), origin, hit.get_point(), &self.normal),
+            _ => 0.0,
+        }
+    }
+}
+
+impl Bounded for OpaqueQuad {
+    fn bounding_box(&self) -> AlignedBox {
+        let opposite: Coord3 = self.corner + self.u + self.v;
+        triangle_bounding_box((&self.corner, &(self.corner + self.u), &opposite))
+            .merge(&triangle_bounding_box((&self.corner, &opposite, &(self.corner + self.v))))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum QuadErr {
+    /// 输入了无效的初始化参数
+    InvalidParamErr,
+}
+
+impl Display for QuadErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidParamErr => write!(f, "invalid quad construction"),
+        }
+    }
+}
+
+impl Error for QuadErr {}
+
+impl QuadErr {
+    pub fn handle(&self) {
+        eprintln!("[Quad Error] {}", self);
+    }
+}
+
+/// 光线方向与平行四边形法向量的点积绝对值小于该值时视为平行
+const PARALLEL_EPSILON: f64 = 1e-12;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 私有函数，位于 z = 0 平面内的斜平行四边形，顶点为原点、`(2, 0, 0)`、`(3, 1, 0)`、`(1, 1, 0)`
+    fn quad() -> OpaqueQuad {
+        OpaqueQuad::new_from(Coord3::new(), Vec3::new_from(2.0, 0.0, 0.0), Vec3::new_from(1.0, 1.0, 0.0)).unwrap()
+    }
+
+    fn downward(x: f64, y: f64) -> Ray {
+        Ray::new_from(Coord3::new_from(x, y, 3.0), Vec3::new_from(0.0, 0.0, -1.0))
+    }
+
+    fn assert_close(v1: &Vec3, v2: &Vec3) {
+        assert!((v1 - v2).magnitude() < 1e-9, "{:?} != {:?}", v1, v2);
+    }
+
+    #[test]
+    fn uv_are_edge_fractions() {
+        let quad: OpaqueQuad = quad();
+        // (2, 0.5) = 0.75·u + 0.5·v
+        let hit = quad.intersection(&downward(2.0, 0.5), FORWARD_T_BOUND).unwrap().unwrap();
+        assert!((hit.get_t() - 3.0).abs() < 1e-9 && hit.is_front_face());
+        assert_close(hit.get_normal(), &Vec3::new_from(0.0, 0.0, 1.0));
+        let (alpha, beta) = hit.get_uv();
+        assert!((alpha - 0.75).abs() < 1e-9 && (beta - 0.5).abs() < 1e-9);
+
+        let up = Ray::new_from(Coord3::new_from(1.0, 0.5, -1.0), Vec3::new_from(0.0, 0.0, 1.0));
+        let hit = quad.intersection(&up, FORWARD_T_BOUND).unwrap().unwrap();
+        assert!((hit.get_t() - 1.0).abs() < 1e-9 && !hit.is_front_face());
+        assert_close(hit.get_normal(), &Vec3::new_from(0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn points_outside_the_parallelogram_miss() {
+        let quad: OpaqueQuad = quad();
+        // 位于外接矩形内、但在斜边之外
+        assert!(quad.intersection(&downward(0.25, 0.75), FORWARD_T_BOUND).unwrap().is_none());
+        assert!(quad.intersection(&downward(2.75, 0.25), FORWARD_T_BOUND).unwrap().is_none());
+        assert!(quad.intersection(&downward(1.0, -0.1), FORWARD_T_BOUND).unwrap().is_none());
+        assert!(quad.intersection(&downward(1.0, 0.5), (0.0, 2.0)).unwrap().is_none());
+    }
+
+    #[test]
+    fn area_and_bounding_box() {
+        let quad: OpaqueQuad = quad();
+        assert!((quad.area() - 2.0).abs() < 1e-9);
+        let bound: AlignedBox = quad.bounding_box();
+        assert!((bound.get_x().0).abs() < 1e-9 && (bound.get_x().1 - 3.0).abs() < 1e-9);
+        assert!((bound.get_y().1 - 1.0).abs() < 1e-9);
+        let (z0, z1) = bound.get_z();
+        assert!(z0 <= 0.0 && 0.0 <= z1);
+    }
+
+    #[test]
+    fn collinear_edges_are_rejected() {
+        let u: Vec3 = Vec3::new_from(1.0, 2.0, 3.0);
+        assert!(OpaqueQuad::new_from(Coord3::new(), u, 2.0 * u).is_err());
+        assert!(OpaqueQuad::new_from(Coord3::new(), u, ZERO_VEC3).is_err());
+    }
+}
@@ -1,18 +1,27 @@
+use super::{Bounded, Object};
+use super::alignedbox::AlignedBox;
+use super::emitter::{Emitter, EmitterSample};
 use super::texture::*;
-use crate::basics::{coord3::Coord3, vec3::Vec3};
-use crate::rays::ray::{Ray, RayIntersectOpaque, RayIntersectErr};
+use crate::basics::{coord3::Coord3, random, vec3::Vec3};
+use crate::rays::hit::HitRecord;
+use crate::rays::ray::{
+    FORWARD_T_BOUND, Ray, RayIntersectErr, RayIntersectOpaque, RayIntersectTransparent, t_in_bound,
+};
+use crate::errors::{MainErr, nan};
 use std::error::Error;
 use std::cmp::Ordering;
+use std::f64::consts::PI;
+use std::fmt::Display;
 
-#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
-struct OpaqueSphere {
+#[derive(Debug, PartialEq, Clone)]
+pub struct OpaqueSphere {
     center: Coord3,
     radius: f64,
     texture: OpaqueTexture,
 }
 
 impl OpaqueSphere {
-    fn new_from(center: Coord3, radius: f64, texture: OpaqueTexture) -> Self {
+    pub fn new_from(center: Coord3, radius: f64, texture: OpaqueTexture) -> Self {
         Self {
             center,
             radius,
@@ -20,16 +29,103 @@ impl OpaqueSphere {
         }
     }
 
-    fn get_center(&self) -> &Coord3 {
+    /// 以原点为球心、半径为 1.0、默认材质为初始值的构建器
+    pub fn builder() -> OpaqueSphereBuilder {
+        OpaqueSphereBuilder {
+            sphere: Self::new_from(Coord3::new(), 1.0, OpaqueTexture::default()),
+        }
+    }
+
+    pub fn get_center(&self) -> &Coord3 {
         &self.center
     }
-    fn get_radius(&self) -> f64 {
+    pub fn get_radius(&self) -> f64 {
         self.radius
     }
+    pub fn get_texture(&self) -> &OpaqueTexture {
+        &self.texture
+    }
+
+    pub fn set_texture(&mut self, texture: OpaqueTexture) -> &mut Self {
+        self.texture = texture;
+        self
+    }
+
+    /// 私有方法，由光线行进时间 `t` 生成交点记录
+    ///
+    /// 纹理坐标取交点的球面坐标：`u` 对应绕 y 轴的方位角，`v` 对应自 -y 轴起的极角
+    fn hit_record(&self, ray: &Ray, t: f64) -> HitRecord<'_> {
+        let outward_normal: Vec3 = (ray.at(t) - self.get_center()).normalize();
+        let phi: f64 = (-outward_normal.z()).atan2(outward_normal.x()) + PI;
+        let theta: f64 = (-outward_normal.y()).acos();
+        let mut hit = HitRecord::new_from(
+            ray,
+            t,
+            outward_normal,
+            (phi / (2.0 * PI), theta / PI),
+            Some(&self.texture),
+        );
+        hit.set_emitter(self.as_emitter());
+        hit
+    }
+
+    /// 私有方法，由球外一点 `origin` 看去，球所张圆锥的半角余弦与圆锥内均匀采样方向的概率密度
+    ///
+    /// `origin` 位于球内或球面上时返回 `None`
+    fn cone_from(&self, origin: &Coord3) -> Option<(f64, f64)> {
+        let distance: f64 = (self.center - origin).magnitude();
+        if distance <= self.radius {
+            return None;
+        }
+        let sin_squared: f64 = (self.radius / distance).powi(2);
+        let cos_theta_max: f64 = (1.0 - sin_squared).sqrt();
+        // 1 - cos_theta_max 的数值稳定写法，避免远处小球的概率密度溢出
+        let solid_angle: f64 = 2.0 * PI * sin_squared / (1.0 + cos_theta_max);
+        Some((cos_theta_max, 1.0 / solid_angle))
+    }
+}
+
+/// `OpaqueSphere` 的构建器，由 `OpaqueSphere::builder` 创建
+#[derive(Debug, PartialEq, Clone)]
+pub struct OpaqueSphereBuilder {
+    sphere: OpaqueSphere,
+}
+
+impl OpaqueSphereBuilder {
+    pub fn center(mut self, center: Coord3) -> Self {
+        self.sphere.center = center;
+        self
+    }
+
+    /// 半径，需为正
+    pub fn radius(mut self, radius: f64) -> Self {
+        self.sphere.radius = radius;
+        self
+    }
+
+    pub fn texture(mut self, texture: OpaqueTexture) -> Self {
+        self.sphere.texture = texture;
+        self
+    }
+
+    /// 检查参数并生成 `OpaqueSphere`
+    ///
+    /// 参数含 `f64::NAN` 时返回 `MainErr`，半径不为正或不是有限值时返回 `SphereErr::InvalidParamErr`
+    pub fn build(self) -> Result<OpaqueSphere, Box<dyn Error>> {
+        let sphere: OpaqueSphere = self.sphere;
+        nan::check::<MainErr>(sphere.center.x(), "OpaqueSphereBuilder::build")?;
+        nan::check::<MainErr>(sphere.center.y(), "OpaqueSphereBuilder::build")?;
+        nan::check::<MainErr>(sphere.center.z(), "OpaqueSphereBuilder::build")?;
+        nan::check::<MainErr>(sphere.radius, "OpaqueSphereBuilder::build")?;
+        if sphere.radius <= 0.0 || !sphere.radius.is_finite() {
+            return Err(Box::new(SphereErr::InvalidParamErr));
+        }
+        Ok(sphere)
+    }
 }
 
 impl RayIntersectOpaque for OpaqueSphere {
-    fn intersection(&self, ray: &Ray) -> Result<Option<Coord3>, Box<dyn Error>> {
+    fn intersection(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<Option<HitRecord<'_>>, Box<dyn Error>> {
         let oc_vec: &Vec3 = &(self.get_center() - ray.get_origin());
 
         if oc_vec.magnitude() < self.get_radius() {
@@ -37,36 +133,122 @@ impl RayIntersectOpaque for OpaqueSphere {
         }
 
         let a: f64 = ray.get_direction() * ray.get_direction();
-        let b: f64 = 2.0 * &(oc_vec * ray.get_direction());
+        let b: f64 = -2.0 * (oc_vec * ray.get_direction());
         let c: f64 = oc_vec * oc_vec - self.get_radius().powi(2);
 
         let quad_eq_delta: f64 = b.powi(2) - 4.0 * a * c;
 
         match quad_eq_delta.total_cmp(&0.0) {
-            Ordering::Less => return Ok(None),
+            Ordering::Less => Ok(None),
             Ordering::Equal => {
                 let t_root: f64 = (-b) / (2.0 * a);
-                let origin: &Vec3 = &ray.get_origin().into();
-                let intersection: Vec3 = origin + t_root * ray.get_direction();
-                return Ok(Some(intersection.into()))
+                if !t_in_bound(t_root, t_bound) {
+                    return Ok(None);
+                }
+                Ok(Some(self.hit_record(ray, t_root)))
             },
             Ordering::Greater => {
                 let t_root1: f64 = ((-b) - quad_eq_delta.sqrt()) / (2.0 * a);
                 let t_root2: f64 = ((-b) + quad_eq_delta.sqrt()) / (2.0 * a);
 
-                if t_root1 < 0.0 || t_root2 < 0.0 {
-                    return Err(Box::new(RayIntersectErr::NegativeRootErr));
-                }
-
-                let min_root = match t_root1 < t_root2 {
-                    true => t_root1,
-                    false => t_root2
-                };
+                // a > 0，故 t_root1 < t_root2，优先取较近且落在区间内的根
+                let root = [t_root1, t_root2]
+                    .into_iter()
+                    .find(|t| t_in_bound(*t, t_bound));
 
-                let origin: &Vec3 = &ray.get_origin().into();
-                let intersection: Vec3 = origin + min_root * ray.get_direction();
-                return Ok(Some(intersection.into()))
+                Ok(root.map(|t| self.hit_record(ray, t)))
             }
         }
     }
 }
+
+impl RayIntersectTransparent for OpaqueSphere {
+    fn enter_n_exit(&self, ray: &Ray) -> Result<Option<(HitRecord<'_>, HitRecord<'_>)>, Box<dyn Error>> {
+        let oc_vec: Vec3 = self.get_center() - ray.get_origin();
+        let half_b: f64 = oc_vec * ray.get_direction();
+        let c: f64 = oc_vec * oc_vec - self.get_radius().powi(2);
+        // 光线方向为单位向量，二次方程化简为 t² - 2·half_b·t + c = 0
+        let quarter_delta: f64 = half_b * half_b - c;
+        if quarter_delta <= 0.0 {
+            return Ok(None);
+        }
+        let t_exit: f64 = half_b + quarter_delta.sqrt();
+        if t_exit <= 0.0 {
+            return Ok(None);
+        }
+        let t_enter: f64 = half_b - quarter_delta.sqrt();
+        Ok(Some((self.hit_record(ray, t_enter), self.hit_record(ray, t_exit))))
+    }
+}
+
+impl Object for OpaqueSphere {
+    fn as_emitter(&self) -> Option<&dyn Emitter> {
+        if self.texture.is_emissive() { Some(self) } else { None }
+    }
+
+    fn as_transparent(&self) -> Option<&dyn RayIntersectTransparent> {
+        Some(self)
+    }
+}
+
+impl Emitter for OpaqueSphere {
+    /// 在由参考点看去球所张的圆锥内均匀采样方向，取该方向与球面的交点
+    ///
+    /// 参考点位于球内时无法采样
+    fn sample_emitter(&self, origin: &Coord3) -> Option<EmitterSample> {
+        let (cos_theta_max, pdf) = self.cone_from(origin)?;
+        let cos_theta: f64 = 1.0 - random::random_f64() * (1.0 - cos_theta_max);
+        let sin_theta: f64 = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+        let phi: f64 = 2.0 * PI * random::random_f64();
+
+        let w: Vec3 = (self.center - origin).normalize();
+        let (u, v) = w.orthonormal_basis();
+        let direction: Vec3 = sin_theta * phi.cos() * u + sin_theta * phi.sin() * v + cos_theta * w;
+
+        // 圆锥边缘的方向可能因舍入误差与球面擦肩而过，此时放弃本次采样
+        let hit = self.intersection(&Ray::new_from(*origin, direction), FORWARD_T_BOUND).ok()??;
+        Some(EmitterSample::new_from(*hit.get_point(), pdf, self.texture.emitted()))
+    }
+
+    fn emitter_pdf(&self, origin: &Coord3, direction: &Vec3) -> f64 {
+        match self.cone_from(origin) {
+            Some((_, pdf)) => match self.intersection(&Ray::new_from(*origin, *direction), FORWARD_T_BOUND) {
+                Ok(Some(_)) => pdf,
+                _ => 0.0,
+            },
+            None => 0.0,
+        }
+    }
+}
+
+impl Bounded for OpaqueSphere {
+    fn bounding_box(&self) -> AlignedBox {
+        let half_diagonal: Vec3 = Vec3::new_from(self.radius, self.radius, self.radius);
+        AlignedBox::from_corners(
+            &(self.center + (-half_diagonal)),
+            &(self.center + half_diagonal),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum SphereErr {
+    /// 输入了无效的初始化参数
+    InvalidParamErr,
+}
+
+impl Display for SphereErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidParamErr => write!(f, "invalid sphere construction"),
+        }
+    }
+}
+
+impl Error for SphereErr {}
+
+impl SphereErr {
+    pub fn handle(&self) {
+        eprintln!("[Sphere Error] {}", self);
+    }
+}
@@ -1,25 +1,357 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::sync::Arc;
+
+use crate::basics::color::{Color, WHITE};
+use crate::basics::random;
+use crate::errors::{MainErr, nan};
+use crate::basics::vec3::Vec3;
+use crate::objects::material::{Dielectric, Lambertian, Metal, Scatter, ScatterRecord};
+use crate::objects::pattern::Texture;
+use crate::rays::hit::HitRecord;
+use crate::rays::ray::Ray;
+
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
 pub enum OpaqueMaterial {
     Plastic,
     Fabric,
     Rubber,
     Wood,
-    Metal,
+    /// 金属，参数为模糊度（`0.0` 为理想镜面）
+    Metal(f64),
+    /// 玻璃等透明电介质，参数为折射率，由 `OpaqueMaterial::glass` 或 `Ior::new_from` 检查后创建
+    Glass(Ior),
     Null
 }
 
+impl OpaqueMaterial {
+    /// 折射率为 `ior` 的玻璃
+    ///
+    /// `ior` 为 `f64::NAN` 时返回 `MainErr`，不为正数或为无穷大时返回 `TextureErr::InvalidIorErr`
+    pub fn glass(ior: f64) -> Result<Self, Box<dyn Error>> {
+        Ok(Self::Glass(Ior::new_from(ior)?))
+    }
+}
+
+/// 玻璃的折射率，只能经 `Ior::new_from` 检查后创建，保证为有限的正数
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct Ior(f64);
+
+impl Ior {
+    /// `ior` 为 `f64::NAN` 时返回 `MainErr`，不为正数或为无穷大时返回 `TextureErr::InvalidIorErr`
+    pub fn new_from(ior: f64) -> Result<Self, Box<dyn Error>> {
+        let ior: f64 = nan::check::<MainErr>(ior, "Ior::new_from")?;
+        if ior <= 0.0 || !ior.is_finite() {
+            return Err(Box::new(TextureErr::InvalidIorErr));
+        }
+        Ok(Self(ior))
+    }
+
+    pub fn get_value(&self) -> f64 {
+        self.0
+    }
+}
+
+impl Scatter for OpaqueMaterial {
+    /// `Metal` 对应 `Metal` 散射模型，`Glass` 对应 `Dielectric`，其余材料均为 `Lambertian`
+    fn scatter(&self, ray: &Ray, hit: &HitRecord<'_>, albedo: Color) -> Option<ScatterRecord> {
+        match self {
+            Self::Metal(fuzz) => Metal::new_from(*fuzz).scatter(ray, hit, albedo),
+            Self::Glass(ior) => Dielectric::new_from(ior.get_value()).scatter(ray, hit, albedo),
+            Self::Plastic | Self::Fabric | Self::Rubber | Self::Wood | Self::Null => {
+                Lambertian.scatter(ray, hit, albedo)
+            }
+        }
+    }
+
+    fn eval(&self, ray: &Ray, hit: &HitRecord<'_>, direction: &Vec3, albedo: Color) -> Color {
+        match self {
+            Self::Metal(fuzz) => Metal::new_from(*fuzz).eval(ray, hit, direction, albedo),
+            Self::Glass(ior) => Dielectric::new_from(ior.get_value()).eval(ray, hit, direction, albedo),
+            Self::Plastic | Self::Fabric | Self::Rubber | Self::Wood | Self::Null => {
+                Lambertian.eval(ray, hit, direction, albedo)
+            }
+        }
+    }
+
+    fn pdf(&self, ray: &Ray, hit: &HitRecord<'_>, direction: &Vec3) -> f64 {
+        match self {
+            Self::Metal(fuzz) => Metal::new_from(*fuzz).pdf(ray, hit, direction),
+            Self::Glass(ior) => Dielectric::new_from(ior.get_value()).pdf(ray, hit, direction),
+            Self::Plastic | Self::Fabric | Self::Rubber | Self::Wood | Self::Null => {
+                Lambertian.pdf(ray, hit, direction)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct OpaqueTexture {
-    /// 材质颜色：RGBA 格式
-    color: (u8, u8, u8, u8),
+    /// 材质颜色：线性浮点 RGB
+    color: Color,
+    /// 随交点变化的表面颜色，设置后代替 `color`
+    pattern: Option<Arc<dyn Texture>>,
+    /// 不透明度，取值介于 0.0（完全透明）到 1.0（完全不透明）
+    alpha: f64,
     /// 反射率
     reflectance: f64,
     /// 材料
     material: OpaqueMaterial,
+    /// 自发光颜色
+    emission: Color,
+    /// 自发光强度，为 `0.0` 时不发光
+    emission_strength: f64,
 }
 
 impl OpaqueTexture {
-    fn new_from(color_tuple: (u8, u8, u8, u8), reflectance: f64, material: OpaqueMaterial) -> Self {
-        Self { color: color_tuple, reflectance, material }
+    /// 由 8 位 RGBA 颜色创建材质，各分量按 `[0, 255]` 线性换算到 `[0, 1]`
+    ///
+    /// 不检查 `reflectance` 的取值；玻璃的折射率由 `Ior` 保证有效
+    pub fn new_from(color_tuple: (u8, u8, u8, u8), reflectance: f64, material: OpaqueMaterial) -> Self {
+        Self {
+            color: Color::new_from(
+                color_tuple.0 as f64 / 255.0,
+                color_tuple.1 as f64 / 255.0,
+                color_tuple.2 as f64 / 255.0,
+            ),
+            pattern: None,
+            alpha: color_tuple.3 as f64 / 255.0,
+            reflectance,
+            material,
+            emission: WHITE,
+            emission_strength: 0.0,
+        }
+    }
+
+    /// 以白色、完全不透明、无反射、不发光、`OpaqueMaterial::Null` 为初始值的构建器
+    pub fn builder() -> OpaqueTextureBuilder {
+        OpaqueTextureBuilder {
+            texture: Self::default(),
+        }
+    }
+
+    pub fn get_color(&self) -> Color {
+        self.color
     }
-}
\ No newline at end of file
+
+    pub fn get_pattern(&self) -> Option<&Arc<dyn Texture>> {
+        self.pattern.as_ref()
+    }
+
+    /// 交点 `hit` 处的表面颜色：设置了 `pattern` 时按纹理求值，否则为 `color`；
+    /// 交点带有顶点颜色时再与之相乘
+    pub fn color_at(&self, hit: &HitRecord<'_>) -> Color {
+        let color: Color = match &self.pattern {
+            Some(pattern) => pattern.value(hit.get_uv(), hit.get_point()),
+            None => self.color,
+        };
+        match hit.get_vertex_color() {
+            Some(vertex_color) => color * vertex_color,
+            None => color,
+        }
+    }
+
+    pub fn get_alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    pub fn get_reflectance(&self) -> f64 {
+        self.reflectance
+    }
+
+    pub fn get_material(&self) -> OpaqueMaterial {
+        self.material
+    }
+
+    pub fn get_emission_color(&self) -> Color {
+        self.emission
+    }
+
+    pub fn get_emission_strength(&self) -> f64 {
+        self.emission_strength
+    }
+
+    /// 表面向外发出的辐射度，即自发光颜色乘以强度
+    ///
+    /// 表面两侧均发光
+    pub fn emitted(&self) -> Color {
+        self.emission * self.emission_strength
+    }
+
+    /// 是否自发光
+    pub fn is_emissive(&self) -> bool {
+        self.emission_strength > 0.0 && !self.emission.is_black()
+    }
+
+    /// 光线 `ray` 在交点 `hit` 处按本材质散射，返回同 `Scatter::scatter`
+    ///
+    /// 光线以 `1 - alpha` 为概率不受影响地穿过表面；
+    /// 非金属、非玻璃材料以 `reflectance` 为概率做镜面反射（表面清漆），否则按材料散射；
+    /// 金属与玻璃的反射由其自身的散射模型决定
+    pub fn scatter(&self, ray: &Ray, hit: &HitRecord<'_>) -> Option<ScatterRecord> {
+        if self.alpha < 1.0 && random::random_f64() >= self.alpha {
+            let passed = Ray::new_from(*hit.get_point(), *ray.get_direction());
+            return Some(ScatterRecord::new_from(WHITE, passed, true));
+        }
+        if random::random_f64() < self.coat_probability() {
+            return Metal::new_from(0.0).scatter(ray, hit, self.color_at(hit));
+        }
+        self.material.scatter(ray, hit, self.color_at(hit))
+    }
+
+    /// 同 `Scatter::eval`，已计入穿透与清漆反射所占的比例
+    pub fn eval(&self, ray: &Ray, hit: &HitRecord<'_>, direction: &Vec3) -> Color {
+        let weight: f64 = self.alpha * (1.0 - self.coat_probability());
+        weight * self.material.eval(ray, hit, direction, self.color_at(hit))
+    }
+
+    /// 同 `Scatter::pdf`，已计入穿透与清漆反射所占的比例
+    pub fn pdf(&self, ray: &Ray, hit: &HitRecord<'_>, direction: &Vec3) -> f64 {
+        let weight: f64 = self.alpha * (1.0 - self.coat_probability());
+        weight * self.material.pdf(ray, hit, direction)
+    }
+
+    /// 私有方法，不透明部分中做清漆反射的概率
+    fn coat_probability(&self) -> f64 {
+        match self.material {
+            OpaqueMaterial::Metal(_) | OpaqueMaterial::Glass(_) => 0.0,
+            _ => self.reflectance,
+        }
+    }
+}
+
+impl Default for OpaqueTexture {
+    fn default() -> Self {
+        Self {
+            color: WHITE,
+            pattern: None,
+            alpha: 1.0,
+            reflectance: 0.0,
+            material: OpaqueMaterial::Null,
+            emission: WHITE,
+            emission_strength: 0.0,
+        }
+    }
+}
+
+impl PartialEq for OpaqueTexture {
+    /// `pattern` 指向同一个纹理对象时视为相同
+    fn eq(&self, other: &Self) -> bool {
+        let same_pattern: bool = match (&self.pattern, &other.pattern) {
+            (Some(p1), Some(p2)) => Arc::ptr_eq(p1, p2),
+            (None, None) => true,
+            _ => false,
+        };
+        same_pattern
+            && self.color == other.color
+            && self.alpha == other.alpha
+            && self.reflectance == other.reflectance
+            && self.material == other.material
+            && self.emission == other.emission
+            && self.emission_strength == other.emission_strength
+    }
+}
+
+/// `OpaqueTexture` 的构建器，由 `OpaqueTexture::builder` 创建
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpaqueTextureBuilder {
+    texture: OpaqueTexture,
+}
+
+impl OpaqueTextureBuilder {
+    /// 线性浮点 RGB 颜色，各分量需非负
+    pub fn color(mut self, color: Color) -> Self {
+        self.texture.color = color;
+        self
+    }
+
+    /// 随交点变化的表面颜色，设置后代替 `color`
+    pub fn pattern(mut self, pattern: Arc<dyn Texture>) -> Self {
+        self.texture.pattern = Some(pattern);
+        self
+    }
+
+    /// 不透明度，取值需在 `[0, 1]` 内
+    pub fn alpha(mut self, alpha: f64) -> Self {
+        self.texture.alpha = alpha;
+        self
+    }
+
+    /// 反射率，取值需在 `[0, 1]` 内
+    pub fn reflectance(mut self, reflectance: f64) -> Self {
+        self.texture.reflectance = reflectance;
+        self
+    }
+
+    /// 材料
+    pub fn material(mut self, material: OpaqueMaterial) -> Self {
+        self.texture.material = material;
+        self
+    }
+
+    /// 自发光颜色，各分量需非负
+    pub fn emission(mut self, emission: Color) -> Self {
+        self.texture.emission = emission;
+        self
+    }
+
+    /// 自发光强度，需非负
+    pub fn emission_strength(mut self, emission_strength: f64) -> Self {
+        self.texture.emission_strength = emission_strength;
+        self
+    }
+
+    /// 检查参数并生成 `OpaqueTexture`
+    ///
+    /// 参数含 `f64::NAN` 时返回 `MainErr`，超出取值范围时返回 `TextureErr::InvalidParamErr`
+    pub fn build(self) -> Result<OpaqueTexture, Box<dyn Error>> {
+        let texture: OpaqueTexture = self.texture;
+        let color: Color = texture.get_color();
+        nan::check::<MainErr>(color.r(), "OpaqueTextureBuilder::build")?;
+        nan::check::<MainErr>(color.g(), "OpaqueTextureBuilder::build")?;
+        nan::check::<MainErr>(color.b(), "OpaqueTextureBuilder::build")?;
+        nan::check::<MainErr>(texture.alpha, "OpaqueTextureBuilder::build")?;
+        nan::check::<MainErr>(texture.reflectance, "OpaqueTextureBuilder::build")?;
+        let emission: Color = texture.get_emission_color();
+        nan::check::<MainErr>(emission.r(), "OpaqueTextureBuilder::build")?;
+        nan::check::<MainErr>(emission.g(), "OpaqueTextureBuilder::build")?;
+        nan::check::<MainErr>(emission.b(), "OpaqueTextureBuilder::build")?;
+        nan::check::<MainErr>(texture.emission_strength, "OpaqueTextureBuilder::build")?;
+
+        if color.r() < 0.0 || color.g() < 0.0 || color.b() < 0.0 {
+            return Err(Box::new(TextureErr::InvalidParamErr));
+        }
+        if emission.r() < 0.0 || emission.g() < 0.0 || emission.b() < 0.0 || texture.emission_strength < 0.0 {
+            return Err(Box::new(TextureErr::InvalidParamErr));
+        }
+        if !(0.0..=1.0).contains(&texture.alpha) || !(0.0..=1.0).contains(&texture.reflectance) {
+            return Err(Box::new(TextureErr::InvalidParamErr));
+        }
+        Ok(texture)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum TextureErr {
+    /// 输入了无效的材质参数
+    InvalidParamErr,
+    /// 玻璃的折射率不是正数
+    InvalidIorErr,
+}
+
+impl Display for TextureErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidParamErr => write!(f, "invalid texture parameter"),
+            Self::InvalidIorErr => write!(f, "index of refraction must be positive"),
+        }
+    }
+}
+
+impl Error for TextureErr {}
+
+impl TextureErr {
+    pub fn handle(&self) {
+        eprintln!("[Texture Error] {}", self);
+    }
+}
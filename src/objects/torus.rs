@@ -0,0 +1,319 @@
+use std::error::Error;
+use std::f64::consts::PI;
+use std::fmt::Display;
+
+use crate::basics::coord3::Coord3;
+use crate::basics::matrix::Matrix3;
+use crate::basics::polynomial::solve_quartic;
+use crate::basics::random;
+use crate::basics::vec3::{Vec3, ZERO_VEC3};
+use crate::errors::{MainErr, nan};
+use crate::objects::alignedbox::AlignedBox;
+use crate::objects::disk::disk_bounding_box;
+use crate::objects::emitter::{Emitter, EmitterSample, area_to_solid_angle};
+use crate::objects::texture::OpaqueTexture;
+use crate::objects::{Bounded, Object};
+use crate::rays::hit::HitRecord;
+use crate::rays::ray::{FORWARD_T_BOUND, Ray, RayIntersectOpaque, t_in_bound};
+
+/// 圆环面：半径为 `minor_radius` 的圆绕过 `center`、方向为 `axis` 的轴旋转一周，圆心轨迹半径为 `major_radius`
+///
+/// 圆环面不是凸体，光线可由内部击中其表面
+#[derive(Debug, PartialEq, Clone)]
+pub struct OpaqueTorus {
+    center: Coord3,
+    /// 旋转轴的单位方向向量
+    axis: Vec3,
+    major_radius: f64,
+    minor_radius: f64,
+    /// 局部坐标系到场景坐标系的旋转，局部坐标系以 `center` 为原点、z 轴沿 `axis`
+    frame: Matrix3,
+    texture: Option<OpaqueTexture>,
+}
+
+impl OpaqueTorus {
+    /// 参数含 `f64::NAN` 时返回 `MainErr`，`axis` 为零向量或两半径不为正时返回 `TorusErr::InvalidParamErr`
+    pub fn new_from(center: Coord3, axis: Vec3, major_radius: f64, minor_radius: f64) -> Result<Self, Box<dyn Error>> {
+        for value in [center.x(), center.y(), center.z(), axis.x(), axis.y(), axis.z(), major_radius, minor_radius] {
+            nan::check::<MainErr>(value, "OpaqueTorus::new_from")?;
+        }
+        let valid = |radius: f64| -> bool { radius > 0.0 && radius.is_finite() };
+        if axis == ZERO_VEC3 || !valid(major_radius) || !valid(minor_radius) {
+            return Err(Box::new(TorusErr::InvalidParamErr));
+        }
+        let axis: Vec3 = axis.normalize();
+        let (u_axis, v_axis) = axis.orthonormal_basis();
+        Ok(Self {
+            center,
+            axis,
+            major_radius,
+            minor_radius,
+            frame: Matrix3::from_cols(&u_axis, &v_axis, &axis),
+            texture: None,
+        })
+    }
+
+    pub fn get_center(&self) -> &Coord3 {
+        &self.center
+    }
+
+    pub fn get_axis(&self) -> &Vec3 {
+        &self.axis
+    }
+
+    pub fn get_major_radius(&self) -> f64 {
+        self.major_radius
+    }
+
+    pub fn get_minor_radius(&self) -> f64 {
+        self.minor_radius
+    }
+
+    pub fn get_texture(&self) -> Option<&OpaqueTexture> {
+        self.texture.as_ref()
+    }
+
+    pub fn set_texture(&mut self, texture: OpaqueTexture) -> &mut Self {
+        self.texture = Some(texture);
+        self
+    }
+
+    /// 圆环面的表面积
+    ///
+    /// 面积元为 `r·|R + r·cos θ|·dθ·dφ`，其中 `θ` 为绕管截面圆心的角度；
+    /// `R < r` 的纺锤形圆环面中 `R + r·cos θ` 可为负，对应自相交形成的内层曲面
+    pub fn area(&self) -> f64 {
+        let (major, minor) = (self.major_radius, self.minor_radius);
+        let ring: f64 = if major >= minor {
+            2.0 * PI * major
+        } else {
+            let alpha: f64 = (-major / minor).acos();
+            4.0 * major * alpha + 4.0 * minor * alpha.sin() - 2.0 * PI * major
+        };
+        2.0 * PI * minor * ring
+    }
+
+    /// 私有方法，光线在局部坐标系中的源点与方向
+    fn to_local(&self, ray: &Ray) -> (Vec3, Vec3) {
+        let inverse: Matrix3 = self.frame.transpose();
+        (
+            inverse.transform_vec(&(ray.get_origin() - self.center)),
+            inverse.transform_vec(ray.get_direction()),
+        )
+    }
+
+    /// 私有方法，由光线行进时间 `t` 生成交点记录
+    ///
+    /// 纹理坐标 `u` 对应绕旋转轴的方位角，`v` 对应绕管截面圆心的角度
+    fn hit_record(&self, ray: &Ray, t: f64) -> HitRecord<'_> {
+        let (o, d) = self.to_local(ray);
+        let p: Vec3 = o + t * d;
+        let rho: f64 = (p.x().powi(2) + p.y().powi(2)).sqrt();
+        // 隐式方程的梯度，在外层曲面上即由管截面圆心指向交点；在纺锤形的奇点处退化为零向量时取轴向
+        let major_squared: f64 = self.major_radius * self.major_radius;
+        let gradient: Vec3 = (p * p + major_squared - self.minor_radius * self.minor_radius) * p
+            - 2.0 * major_squared * Vec3::new_from(p.x(), p.y(), 0.0);
+        let local_normal: Vec3 = if gradient == ZERO_VEC3 {
+            Vec3::new_from(0.0, 0.0, p.z().signum())
+        } else {
+            gradient
+        };
+        let outward_normal: Vec3 = self.frame.transform_vec(&local_normal).normalize();
+        let uv: (f64, f64) = (
+            p.y().atan2(p.x()).rem_euclid(2.0 * PI) / (2.0 * PI),
+            p.z().atan2(rho - self.major_radius).rem_euclid(2.0 * PI) / (2.0 * PI),
+        );
+        let mut hit = HitRecord::new_from(ray, t, outward_normal, uv, self.texture.as_ref());
+        hit.set_emitter(self.as_emitter());
+        hit
+    }
+}
+
+impl RayIntersectOpaque for OpaqueTorus {
+    /// 局部坐标系中圆环面满足 `(x² + y² + z² + R² - r²)² = 4R²·(x² + y²)`，代入光线方程得到关于 `t` 的四次方程
+    fn intersection(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<Option<HitRecord<'_>>, Box<dyn Error>> {
+        let (o, d) = self.to_local(ray);
+        let (major, minor) = (self.major_radius, self.minor_radius);
+        // 光线源点较远时，先沿光线移动到包围球附近再求解，减小四次方程系数的量级差异
+        let t_shift: f64 = (-(o * d) - (major + minor)).max(0.0);
+        let o: Vec3 = o + t_shift * d;
+
+        let e: f64 = o * o + major * major - minor * minor;
+        let f: f64 = o * d;
+        let four_major_squared: f64 = 4.0 * major * major;
+        let roots: Vec<f64> = solve_quartic(
+            1.0,
+            4.0 * f,
+            4.0 * f * f + 2.0 * e - four_major_squared * (d.x().powi(2) + d.y().powi(2)),
+            4.0 * e * f - 2.0 * four_major_squared * (o.x() * d.x() + o.y() * d.y()),
+            e * e - four_major_squared * (o.x().powi(2) + o.y().powi(2)),
+        );
+        Ok(roots
+            .into_iter()
+            .map(|root| root + t_shift)
+            .find(|t| t_in_bound(*t, t_bound))
+            .map(|t| self.hit_record(ray, t)))
+    }
+}
+
+impl Object for OpaqueTorus {
+    fn as_emitter(&self) -> Option<&dyn Emitter> {
+        match &self.texture {
+            Some(texture) if texture.is_emissive() => Some(self),
+            _ => None,
+        }
+    }
+}
+
+impl Emitter for OpaqueTorus {
+    /// 在圆环面上按面积均匀采样：方位角 `φ` 均匀选取，管截面角度 `θ` 按 `|R + r·cos θ|` 拒绝采样
+    fn sample_emitter(&self, origin: &Coord3) -> Option<EmitterSample> {
+        let texture: &OpaqueTexture = self.texture.as_ref()?;
+        let (major, minor) = (self.major_radius, self.minor_radius);
+        let theta: f64 = loop {
+            let theta: f64 = 2.0 * PI * random::random_f64();
+            if random::random_f64() * (major + minor) <= (major + minor * theta.cos()).abs() {
+                break theta;
+            }
+        };
+        let phi: f64 = 2.0 * PI * random::random_f64();
+        let rho: f64 = major + minor * theta.cos();
+        let local_point: Vec3 = Vec3::new_from(rho * phi.cos(), rho * phi.sin(), minor * theta.sin());
+        let local_normal: Vec3 = Vec3::new_from(theta.cos() * phi.cos(), theta.cos() * phi.sin(), theta.sin());
+        let point: Coord3 = self.center + self.frame.transform_vec(&local_point);
+        let normal: Vec3 = self.frame.transform_vec(&local_normal);
+
+        let pdf: f64 = area_to_solid_angle(1.0 / self.area(), origin, &point, &normal);
+        if pdf <= 0.0 {
+            return None;
+        }
+        Some(EmitterSample::new_from(point, pdf, texture.emitted()))
+    }
+
+    fn emitter_pdf(&self, origin: &Coord3, direction: &Vec3) -> f64 {
+        match self.intersection(&Ray::new_from(*origin, *direction), FORWARD_T_BOUND) {
+            Ok(Some(hit)) => area_to_solid_angle(1.0 / self.area(), origin, hit.get_point(), hit.get_normal()),
+            _ => 0.0,
+        }
+    }
+}
+
+impl Bounded for OpaqueTorus {
+    /// 管截面圆心轨迹的包围盒向各方向扩展 `minor_radius`
+    fn bounding_box(&self) -> AlignedBox {
+        let ring: AlignedBox = disk_bounding_box(&self.center, &self.axis, self.major_radius);
+        let padding: Vec3 = Vec3::new_from(self.minor_radius, self.minor_radius, self.minor_radius);
+        AlignedBox::from_corners(&(ring.get_min() + (-padding)), &(ring.get_max() + padding))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum TorusErr {
+    /// 输入了无效的初始化参数
+    InvalidParamErr,
+}
+
+impl Display for TorusErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidParamErr => write!(f, "invalid torus construction"),
+        }
+    }
+}
+
+impl Error for TorusErr {}
+
+impl TorusErr {
+    pub fn handle(&self) {
+        eprintln!("[Torus Error] {}", self);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 私有函数，中心为原点、旋转轴沿 z 轴的圆环面
+    fn torus(major_radius: f64, minor_radius: f64) -> OpaqueTorus {
+        OpaqueTorus::new_from(Coord3::new_from(0.0, 0.0, 0.0), Vec3::new_from(0.0, 0.0, 1.0), major_radius, minor_radius)
+            .unwrap()
+    }
+
+    fn leftward(x: f64) -> Ray {
+        Ray::new_from(Coord3::new_from(x, 0.0, 0.0), Vec3::new_from(-1.0, 0.0, 0.0))
+    }
+
+    fn assert_close(v1: &Vec3, v2: &Vec3) {
+        assert!((v1 - v2).magnitude() < 1e-9, "{:?} != {:?}", v1, v2);
+    }
+
+    #[test]
+    fn equators_are_hit_in_order() {
+        let ring: OpaqueTorus = torus(2.0, 0.5);
+        let ray: Ray = leftward(5.0);
+        // 依次为外赤道进入、内赤道离开、另一侧内赤道进入、外赤道离开
+        let expected: [(f64, bool); 4] = [(2.5, true), (3.5, false), (6.5, true), (7.5, false)];
+        let mut t_min: f64 = 0.0;
+        for (t, front_face) in expected {
+            let hit = ring.intersection(&ray, (t_min, f64::INFINITY)).unwrap().unwrap();
+            assert!((hit.get_t() - t).abs() < 1e-9, "{} != {}", hit.get_t(), t);
+            assert_eq!(hit.is_front_face(), front_face);
+            assert_close(hit.get_normal(), &Vec3::new_from(1.0, 0.0, 0.0));
+            t_min = t + 0.1;
+        }
+        assert!(ring.intersection(&ray, (t_min, f64::INFINITY)).unwrap().is_none());
+
+        // 由中心的孔洞向外发出的光线先击中内赤道
+        let outward = Ray::new_from(Coord3::new_from(0.0, 0.0, 0.0), Vec3::new_from(1.0, 0.0, 0.0));
+        let hit = ring.intersection(&outward, FORWARD_T_BOUND).unwrap().unwrap();
+        assert!((hit.get_t() - 1.5).abs() < 1e-9 && hit.is_front_face());
+        assert_close(hit.get_normal(), &Vec3::new_from(-1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn ray_through_the_hole_misses() {
+        let ring: OpaqueTorus = torus(2.0, 0.5);
+        let down = Ray::new_from(Coord3::new_from(0.0, 0.0, 5.0), Vec3::new_from(0.0, 0.0, -1.0));
+        assert!(ring.intersection(&down, FORWARD_T_BOUND).unwrap().is_none());
+        let tilted = Ray::new_from(Coord3::new_from(1.0, 0.0, 5.0), Vec3::new_from(-0.2, 0.0, -1.0));
+        assert!(ring.intersection(&tilted, FORWARD_T_BOUND).unwrap().is_none());
+    }
+
+    #[test]
+    fn far_away_origin_is_shifted() {
+        let ring: OpaqueTorus = torus(2.0, 0.5);
+        let hit = ring.intersection(&leftward(1e6), FORWARD_T_BOUND).unwrap().unwrap();
+        assert!((hit.get_t() - (1e6 - 2.5)).abs() < 1e-6);
+        assert!((hit.get_point() - Coord3::new_from(2.5, 0.0, 0.0)).magnitude() < 1e-6);
+        assert_close(hit.get_normal(), &Vec3::new_from(1.0, 0.0, 0.0));
+        assert!(ring.intersection(&leftward(-1e6), FORWARD_T_BOUND).unwrap().is_none());
+    }
+
+    #[test]
+    fn spindle_inner_surface_bounds_a_cavity() {
+        // R = 0.4 < r = 0.6，中心处为自相交形成的内层曲面所围的空腔，其边界在 x = r - R 处
+        let spindle: OpaqueTorus = torus(0.4, 0.6);
+        let outward = Ray::new_from(Coord3::new_from(0.0, 0.0, 0.0), Vec3::new_from(1.0, 0.0, 0.0));
+        let hit = spindle.intersection(&outward, FORWARD_T_BOUND).unwrap().unwrap();
+        assert!((hit.get_t() - 0.2).abs() < 1e-9 && hit.is_front_face());
+        assert_close(hit.get_normal(), &Vec3::new_from(-1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn area_matches_closed_form_and_integral() {
+        let ring: OpaqueTorus = torus(2.0, 0.5);
+        assert!((ring.area() - 4.0 * PI * PI * 2.0 * 0.5).abs() < 1e-9);
+
+        // 纺锤形圆环面按面积元 2π·r·|R + r·cos θ|·dθ 数值积分
+        let spindle: OpaqueTorus = torus(0.4, 0.6);
+        let steps: usize = 100_000;
+        let d_theta: f64 = 2.0 * PI / steps as f64;
+        let integral: f64 = (0..steps)
+            .map(|i| {
+                let theta: f64 = (i as f64 + 0.5) * d_theta;
+                2.0 * PI * 0.6 * (0.4 + 0.6 * theta.cos()).abs() * d_theta
+            })
+            .sum();
+        assert!((spindle.area() - integral).abs() < 1e-6);
+    }
+}
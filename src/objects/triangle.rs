@@ -1,53 +1,235 @@
 use std::{error::Error, fmt::Display};
 
 use crate::basics::{
+    color::Color,
     coord3::Coord3,
+    random,
     vec3::*,
 };
-use crate::rays::ray::{Ray, RayIntersectOpaque};
+use crate::objects::{Bounded, Object};
+use crate::objects::alignedbox::AlignedBox;
+use crate::objects::emitter::{Emitter, EmitterSample, area_to_solid_angle};
+use crate::objects::texture::OpaqueTexture;
+use crate::rays::hit::HitRecord;
+use crate::rays::ray::{FORWARD_T_BOUND, Ray, RayIntersectOpaque, t_in_bound};
 
-#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct OpaqueTriangle {
     p1: Coord3,
     p2: Coord3,
     p3: Coord3,
+    /// 三个顶点处朝向外侧的法向量，用于插值出平滑的着色法向量
+    normals: Option<[Vec3; 3]>,
+    /// 三个顶点处的纹理坐标
+    uvs: Option<[(f64, f64); 3]>,
+    /// 三个顶点处的颜色，插值结果与材质颜色相乘作为反照率
+    colors: Option<[Color; 3]>,
+    texture: Option<OpaqueTexture>,
 }
 
 impl OpaqueTriangle {
     pub fn new_from(p1: Coord3, p2: Coord3, p3: Coord3) -> Result<Self, Box<dyn Error>> {
         let ab: Vec3 = p2 - p1;
         let ac: Vec3 = p3 - p1;
-        match (&ab).cross(&ac) {
-            ZERO_VEC3 => return Err(Box::new(TriagErr::InvalidParamErr)),
-            _ => (),
+        if ab.cross(&ac) == ZERO_VEC3 {
+            return Err(Box::new(TriagErr::InvalidParamErr));
         }
 
-        Ok(Self { p1, p2, p3 })
+        Ok(Self {
+            p1,
+            p2,
+            p3,
+            normals: None,
+            uvs: None,
+            colors: None,
+            texture: None,
+        })
+    }
+
+    pub fn get_points(&self) -> (&Coord3, &Coord3, &Coord3) {
+        (&self.p1, &self.p2, &self.p3)
+    }
+
+    pub fn get_vertex_normals(&self) -> Option<&[Vec3; 3]> {
+        self.normals.as_ref()
+    }
+
+    /// 三个顶点处朝向外侧的法向量，无需为单位向量
+    pub fn set_vertex_normals(&mut self, normals: [Vec3; 3]) -> &mut Self {
+        self.normals = Some(normals);
+        self
+    }
+
+    pub fn get_vertex_uvs(&self) -> Option<&[(f64, f64); 3]> {
+        self.uvs.as_ref()
+    }
+
+    pub fn set_vertex_uvs(&mut self, uvs: [(f64, f64); 3]) -> &mut Self {
+        self.uvs = Some(uvs);
+        self
+    }
+
+    pub fn get_texture(&self) -> Option<&OpaqueTexture> {
+        self.texture.as_ref()
+    }
+
+    pub fn get_vertex_colors(&self) -> Option<&[Color; 3]> {
+        self.colors.as_ref()
+    }
+
+    pub fn set_vertex_colors(&mut self, colors: [Color; 3]) -> &mut Self {
+        self.colors = Some(colors);
+        self
+    }
+
+    pub fn set_texture(&mut self, texture: OpaqueTexture) -> &mut Self {
+        self.texture = Some(texture);
+        self
+    }
+
+    /// 三角形的面积
+    pub fn area(&self) -> f64 {
+        0.5 * (self.p2 - self.p1).cross(&(self.p3 - self.p1)).magnitude()
     }
 }
 
 impl RayIntersectOpaque for OpaqueTriangle {
-    fn intersection(&self, ray: &Ray) -> Result<Option<Coord3>, Box<dyn Error>> {
-        let e1 = self.p2 - self.p1;
-        let e2 = self.p3 - self.p1;
-        let s = ray.get_origin() - self.p1;
-        let s1 = ray.get_direction().cross(&e2);
-        let s2 = s.cross(&e1);
-
-        let reciproc_s1_dot_e1 = 1.0 / (s1 * e1);
-
-        let t = s2 * e2 * reciproc_s1_dot_e1;
-        let b1 = s1 * s * reciproc_s1_dot_e1;
-        let b2 = s2 * ray.get_direction() * reciproc_s1_dot_e1;
-
-        if t >= 0.0 && b1 > 0.0 && b2 > 0.0 && b1 + b2 < 1.0 {
-            let origin: &Vec3 = &ray.get_origin().into();
-            let intersection: Vec3 = origin + t * ray.get_direction();
-            return Ok(Some(intersection.into()));
+    /// Möller–Trumbore 算法
+    ///
+    /// 几何法向量为 `(p2 - p1) × (p3 - p1)` 方向，设置了顶点法向量时交点记录的着色法向量为其插值；
+    /// 纹理坐标为顶点纹理坐标的插值，未设置时为重心坐标 `(b1, b2)`；设置了顶点颜色时交点记录其插值
+    fn intersection(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<Option<HitRecord<'_>>, Box<dyn Error>> {
+        match moller_trumbore((&self.p1, &self.p2, &self.p3), ray, t_bound) {
+            Some((t, b1, b2)) => {
+                let outward_normal: Vec3 = (self.p2 - self.p1).cross(&(self.p3 - self.p1)).normalize();
+                let uv: (f64, f64) = match &self.uvs {
+                    Some(uvs) => interpolate_uv(uvs, b1, b2),
+                    None => (b1, b2),
+                };
+                let mut hit = HitRecord::new_from(ray, t, outward_normal, uv, self.texture.as_ref());
+                if let Some(shading_normal) = self.normals.as_ref().and_then(|n| interpolate_normal(n, b1, b2)) {
+                    hit.set_shading_normal(shading_normal);
+                }
+                hit.set_vertex_color(self.colors.map(|colors| interpolate_color(&colors, b1, b2)));
+                hit.set_emitter(self.as_emitter());
+                Ok(Some(hit))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+impl Object for OpaqueTriangle {
+    fn as_emitter(&self) -> Option<&dyn Emitter> {
+        match &self.texture {
+            Some(texture) if texture.is_emissive() => Some(self),
+            _ => None,
+        }
+    }
+}
+
+impl Emitter for OpaqueTriangle {
+    /// 在三角形上按面积均匀采样
+    fn sample_emitter(&self, origin: &Coord3) -> Option<EmitterSample> {
+        let texture: &OpaqueTexture = self.texture.as_ref()?;
+        let e1: Vec3 = self.p2 - self.p1;
+        let e2: Vec3 = self.p3 - self.p1;
+        let sqrt_r1: f64 = random::random_f64().sqrt();
+        let r2: f64 = random::random_f64();
+        let point: Coord3 = self.p1 + sqrt_r1 * (1.0 - r2) * e1 + sqrt_r1 * r2 * e2;
+
+        let normal: Vec3 = e1.cross(&e2).normalize();
+        let pdf: f64 = area_to_solid_angle(1.0 / self.area(), origin, &point, &normal);
+        if pdf <= 0.0 {
+            return None;
+        }
+        Some(EmitterSample::new_from(point, pdf, texture.emitted()))
+    }
+
+    fn emitter_pdf(&self, origin: &Coord3, direction: &Vec3) -> f64 {
+        match self.intersection(&Ray::new_from(*origin, *direction), FORWARD_T_BOUND) {
+            Ok(Some(hit)) => area_to_solid_angle(1.0 / self.area(), origin, hit.get_point(), hit.get_normal()),
+            _ => 0.0,
+        }
+    }
+}
+
+impl Bounded for OpaqueTriangle {
+    fn bounding_box(&self) -> AlignedBox {
+        triangle_bounding_box((&self.p1, &self.p2, &self.p3))
+    }
+}
+
+/// Möller–Trumbore 算法求光线 `ray` 在 `t_bound` 区间内与三角形 `(p1, p2, p3)` 的交点
+///
+/// 相交时返回 `(t, b1, b2)`，交点为 `(1 - b1 - b2) * p1 + b1 * p2 + b2 * p3`
+pub fn moller_trumbore(
+    points: (&Coord3, &Coord3, &Coord3),
+    ray: &Ray,
+    t_bound: (f64, f64),
+) -> Option<(f64, f64, f64)> {
+    let (p1, p2, p3) = points;
+    let e1 = p2 - p1;
+    let e2 = p3 - p1;
+    let s = ray.get_origin() - p1;
+    let s1 = ray.get_direction().cross(&e2);
+    let s2 = s.cross(&e1);
+
+    let reciproc_s1_dot_e1 = 1.0 / (s1 * e1);
+
+    let t = s2 * e2 * reciproc_s1_dot_e1;
+    let b1 = s1 * s * reciproc_s1_dot_e1;
+    let b2 = s2 * ray.get_direction() * reciproc_s1_dot_e1;
+
+    if t_in_bound(t, t_bound) && b1 > 0.0 && b2 > 0.0 && b1 + b2 < 1.0 {
+        Some((t, b1, b2))
+    } else {
+        None
+    }
+}
+
+/// 三角形 `(p1, p2, p3)` 的包围盒
+///
+/// 与坐标轴平面平行的三角形，其包围盒在该轴上会向两侧略微扩展，避免厚度为零
+pub fn triangle_bounding_box(points: (&Coord3, &Coord3, &Coord3)) -> AlignedBox {
+    let (p1, p2, p3) = points;
+    let bound = |v1: f64, v2: f64, v3: f64| -> (f64, f64) {
+        let (min, max) = (v1.min(v2).min(v3), v1.max(v2).max(v3));
+        if max - min < BOUND_PADDING {
+            (min - BOUND_PADDING, max + BOUND_PADDING)
         } else {
-            return Ok(None);
+            (min, max)
         }
+    };
+    let (x0, x1) = bound(p1.x(), p2.x(), p3.x());
+    let (y0, y1) = bound(p1.y(), p2.y(), p3.y());
+    let (z0, z1) = bound(p1.z(), p2.z(), p3.z());
+    AlignedBox::from_corners(&Coord3::new_from(x0, y0, z0), &Coord3::new_from(x1, y1, z1))
+}
+
+/// 按重心坐标 `(b1, b2)` 插值三个顶点处的法向量，结果为单位向量
+///
+/// 插值结果为零向量时返回 `None`
+pub fn interpolate_normal(normals: &[Vec3; 3], b1: f64, b2: f64) -> Option<Vec3> {
+    let normal: Vec3 = (1.0 - b1 - b2) * normals[0] + b1 * normals[1] + b2 * normals[2];
+    if normal == ZERO_VEC3 {
+        return None;
     }
+    Some(normal.normalize())
+}
+
+/// 按重心坐标 `(b1, b2)` 插值三个顶点处的纹理坐标
+pub fn interpolate_uv(uvs: &[(f64, f64); 3], b1: f64, b2: f64) -> (f64, f64) {
+    let b0: f64 = 1.0 - b1 - b2;
+    (
+        b0 * uvs[0].0 + b1 * uvs[1].0 + b2 * uvs[2].0,
+        b0 * uvs[0].1 + b1 * uvs[1].1 + b2 * uvs[2].1,
+    )
+}
+
+/// 按重心坐标 `(b1, b2)` 插值三个顶点处的颜色
+pub fn interpolate_color(colors: &[Color; 3], b1: f64, b2: f64) -> Color {
+    (1.0 - b1 - b2) * colors[0] + b1 * colors[1] + b2 * colors[2]
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -70,3 +252,5 @@ impl TriagErr {
         eprintln!("[Triangle Error] {}", self);
     }
 }
+
+const BOUND_PADDING: f64 = 1e-6;
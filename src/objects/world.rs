@@ -0,0 +1,200 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::sync::Arc;
+
+use crate::objects::Object;
+use crate::objects::alignedbox::AlignedBox;
+use crate::objects::bvh::Bvh;
+use crate::objects::emitter::Emitter;
+use crate::objects::light::Light;
+use crate::rays::hit::HitRecord;
+use crate::rays::ray::{Ray, RayIntersectOpaque};
+
+/// 场景容器，持有任意实现了 `Object` 的物体与任意实现了 `Light` 的光源
+#[derive(Default)]
+pub struct World {
+    objects: Vec<Box<dyn Object>>,
+    lights: Vec<Box<dyn Light>>,
+    /// 自发光物体，与 `objects` 共享所有权，供积分器直接采样
+    emitters: Vec<Arc<dyn Object>>,
+}
+
+impl World {
+    pub fn new() -> Self {
+        Self {
+            objects: Vec::new(),
+            lights: Vec::new(),
+            emitters: Vec::new(),
+        }
+    }
+
+    /// 向场景中添加一个物体
+    ///
+    /// 物体自发光（`Object::as_emitter` 返回 `Some`）时同时登记为发光体；
+    /// 放入 `Bvh` 等容器后再添加的自发光物体不会被登记，只能靠光线偶然击中
+    pub fn push(&mut self, object: Box<dyn Object>) -> &mut Self {
+        if object.as_emitter().is_some() {
+            let shared: Arc<dyn Object> = Arc::from(object);
+            self.emitters.push(Arc::clone(&shared));
+            self.objects.push(Box::new(shared));
+        } else {
+            self.objects.push(object);
+        }
+        self
+    }
+
+    /// 向场景中添加一个光源
+    pub fn push_light(&mut self, light: Box<dyn Light>) -> &mut Self {
+        self.lights.push(light);
+        self
+    }
+
+    pub fn get_lights(&self) -> &[Box<dyn Light>] {
+        &self.lights
+    }
+
+    /// 已登记的发光体数量
+    pub fn emitter_count(&self) -> usize {
+        self.emitters.len()
+    }
+
+    /// 第 `idx` 个已登记的发光体
+    pub fn get_emitter(&self, idx: usize) -> Option<&dyn Emitter> {
+        self.emitters.get(idx).and_then(|object| object.as_emitter())
+    }
+
+    /// 在已登记的发光体中等概率选取一个时，选中 `emitter` 的概率；`emitter` 未登记时返回 `0.0`
+    pub fn emitter_select_pdf(&self, emitter: &dyn Emitter) -> f64 {
+        let registered: bool = self.emitters.iter().any(|object| match object.as_emitter() {
+            Some(registered) => std::ptr::addr_eq(registered, emitter),
+            None => false,
+        });
+        if registered { 1.0 / self.emitters.len() as f64 } else { 0.0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// 包围场景中全部物体的轴对齐盒，场景为空时返回 `None`
+    pub fn bounding_box(&self) -> Option<AlignedBox> {
+        self.objects
+            .iter()
+            .map(|object| object.bounding_box())
+            .reduce(|b1, b2| b1.merge(&b2))
+    }
+
+    /// 场景统计信息
+    pub fn stats(&self) -> WorldStats {
+        WorldStats {
+            object_count: self.objects.len(),
+            bound: self.bounding_box(),
+            total_bound_area: self
+                .objects
+                .iter()
+                .map(|object| object.bounding_box().surface_area())
+                .sum(),
+        }
+    }
+
+    /// 将场景中现有的有界物体组织为一棵 `Bvh`，加速后续的交点查询
+    ///
+    /// 包围盒无界的物体（如平面）会使整棵树的包围盒失去剔除作用，保留在顶层逐个求交；
+    /// 没有有界物体时不做任何处理
+    pub fn build_bvh(&mut self) -> Result<&mut Self, Box<dyn Error>> {
+        let objects: Vec<Box<dyn Object>> = std::mem::take(&mut self.objects);
+        let mut bounded: Vec<Box<dyn Object>> = Vec::new();
+        for object in objects {
+            if object.bounding_box().is_bounded() {
+                bounded.push(object);
+            } else {
+                self.objects.push(object);
+            }
+        }
+        if !bounded.is_empty() {
+            self.objects.push(Box::new(Bvh::new_from(bounded)?));
+        }
+        Ok(self)
+    }
+
+    /// 返回光线在 `t_bound` 区间内最先碰到的物体的交点记录
+    ///
+    /// 以交点记录中光线行进的时间（即距离）比较远近，
+    /// 每找到一个更近的交点就收缩后续物体的搜索区间；
+    /// 各物体按 `Object::hit` 求交：光线由可穿透物体内部发出时取其离开点，由其余物体内部发出时视为未击中
+    pub fn closest_hit(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<Option<HitRecord<'_>>, Box<dyn Error>> {
+        let mut closest: Option<HitRecord<'_>> = None;
+        let mut t_max: f64 = t_bound.1;
+
+        for object in &self.objects {
+            if let Some(hit) = object.hit(ray, (t_bound.0, t_max))? {
+                t_max = hit.get_t();
+                closest = Some(hit);
+            }
+        }
+
+        Ok(closest)
+    }
+
+    /// 光线在 `t_bound` 区间内是否与任意物体相交，找到第一个交点即返回
+    pub fn any_hit(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<bool, Box<dyn Error>> {
+        for object in &self.objects {
+            if object.any_hit(ray, t_bound)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+impl RayIntersectOpaque for World {
+    fn intersection(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<Option<HitRecord<'_>>, Box<dyn Error>> {
+        self.closest_hit(ray, t_bound)
+    }
+}
+
+/// 场景统计信息，由 `World::stats` 生成
+#[derive(Debug, PartialEq, Clone)]
+pub struct WorldStats {
+    /// 场景顶层的物体数（建立 `Bvh` 后整棵树计为一个物体）
+    object_count: usize,
+    /// 整个场景的包围盒
+    bound: Option<AlignedBox>,
+    /// 各物体包围盒表面积之和
+    total_bound_area: f64,
+}
+
+impl WorldStats {
+    pub fn get_object_count(&self) -> usize {
+        self.object_count
+    }
+
+    pub fn get_bound(&self) -> Option<&AlignedBox> {
+        self.bound.as_ref()
+    }
+
+    pub fn get_total_bound_area(&self) -> f64 {
+        self.total_bound_area
+    }
+}
+
+impl Display for WorldStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} object(s)", self.object_count)?;
+        if let Some(bound) = &self.bound {
+            write!(
+                f,
+                ", bound x{:?} y{:?} z{:?}, total bound area {:.3}",
+                bound.get_x(),
+                bound.get_y(),
+                bound.get_z(),
+                self.total_bound_area
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,162 @@
+use crate::basics::color::Color;
+use crate::basics::coord3::Coord3;
+use crate::basics::vec3::Vec3;
+use crate::objects::emitter::Emitter;
+use crate::objects::medium::ConstantMedium;
+use crate::objects::texture::OpaqueTexture;
+use crate::rays::ray::Ray;
+
+/// 光线与物体相交的完整记录
+#[derive(Debug, Clone, Copy)]
+pub struct HitRecord<'a> {
+    /// 交点坐标
+    point: Coord3,
+    /// 交点处的单位几何法向量，总是与入射光线方向相对
+    normal: Vec3,
+    /// 交点处的单位着色法向量，仅用于散射模型的计算，与 `normal` 位于表面同一侧
+    shading_normal: Vec3,
+    /// 光线由源点行进到交点的时间（光线方向为单位向量，即为距离）
+    t: f64,
+    /// 光线是否从物体外侧（法向量朝外的一侧）射入
+    front_face: bool,
+    /// 交点处的纹理坐标 `(u, v)`，取值介于 0.0 到 1.0
+    uv: (f64, f64),
+    /// 被击中物体的材质
+    texture: Option<&'a OpaqueTexture>,
+    /// 由顶点颜色插值得到的交点颜色，与材质颜色相乘作为反照率
+    vertex_color: Option<Color>,
+    /// 被击中物体自发光时，可供直接采样的发光体
+    emitter: Option<&'a dyn Emitter>,
+    /// 交点为参与介质内部的碰撞点时，所在的介质
+    medium: Option<&'a ConstantMedium>,
+}
+
+impl<'a> HitRecord<'a> {
+    /// 由光线 `ray`、行进时间 `t` 与交点处朝向物体外侧的单位法向量 `outward_normal` 创建交点记录
+    ///
+    /// 法向量会被翻转为与入射光线方向相对，并据此记录 `front_face`；着色法向量默认与几何法向量相同
+    pub fn new_from(
+        ray: &Ray,
+        t: f64,
+        outward_normal: Vec3,
+        uv: (f64, f64),
+        texture: Option<&'a OpaqueTexture>,
+    ) -> Self {
+        let front_face: bool = ray.get_direction() * outward_normal < 0.0;
+        let normal: Vec3 = if front_face {
+            outward_normal
+        } else {
+            -outward_normal
+        };
+        Self {
+            point: ray.at(t),
+            normal,
+            shading_normal: normal,
+            t,
+            front_face,
+            uv,
+            texture,
+            vertex_color: None,
+            emitter: None,
+            medium: None,
+        }
+    }
+
+    pub fn get_point(&self) -> &Coord3 {
+        &self.point
+    }
+
+    pub fn get_normal(&self) -> &Vec3 {
+        &self.normal
+    }
+
+    pub fn get_shading_normal(&self) -> &Vec3 {
+        &self.shading_normal
+    }
+
+    /// 设置朝向物体外侧的单位着色法向量 `shading_normal`（如由顶点法向量插值得到）
+    ///
+    /// 着色法向量会被翻转到与几何法向量相同的一侧；几何法向量与 `front_face` 不受影响
+    pub fn set_shading_normal(&mut self, shading_normal: Vec3) -> &mut Self {
+        self.shading_normal = if shading_normal * self.normal < 0.0 {
+            -shading_normal
+        } else {
+            shading_normal
+        };
+        self
+    }
+
+    pub fn get_t(&self) -> f64 {
+        self.t
+    }
+
+    pub fn is_front_face(&self) -> bool {
+        self.front_face
+    }
+
+    pub fn get_uv(&self) -> (f64, f64) {
+        self.uv
+    }
+
+    pub fn get_texture(&self) -> Option<&'a OpaqueTexture> {
+        self.texture
+    }
+
+    pub fn set_texture(&mut self, texture: Option<&'a OpaqueTexture>) -> &mut Self {
+        self.texture = texture;
+        self
+    }
+
+    pub fn get_vertex_color(&self) -> Option<Color> {
+        self.vertex_color
+    }
+
+    pub fn set_vertex_color(&mut self, vertex_color: Option<Color>) -> &mut Self {
+        self.vertex_color = vertex_color;
+        self
+    }
+
+    pub fn get_emitter(&self) -> Option<&'a dyn Emitter> {
+        self.emitter
+    }
+
+    pub fn set_emitter(&mut self, emitter: Option<&'a dyn Emitter>) -> &mut Self {
+        self.emitter = emitter;
+        self
+    }
+
+    pub fn get_medium(&self) -> Option<&'a ConstantMedium> {
+        self.medium
+    }
+
+    pub fn set_medium(&mut self, medium: Option<&'a ConstantMedium>) -> &mut Self {
+        self.medium = medium;
+        self
+    }
+}
+
+impl PartialEq for HitRecord<'_> {
+    /// `emitter` 与 `medium` 分别指向同一个发光体与介质时视为相同
+    fn eq(&self, other: &Self) -> bool {
+        let same_emitter: bool = match (self.emitter, other.emitter) {
+            (Some(e1), Some(e2)) => std::ptr::addr_eq(e1, e2),
+            (None, None) => true,
+            _ => false,
+        };
+        let same_medium: bool = match (self.medium, other.medium) {
+            (Some(m1), Some(m2)) => std::ptr::eq(m1, m2),
+            (None, None) => true,
+            _ => false,
+        };
+        same_emitter
+            && same_medium
+            && self.point == other.point
+            && self.normal == other.normal
+            && self.shading_normal == other.shading_normal
+            && self.t == other.t
+            && self.front_face == other.front_face
+            && self.uv == other.uv
+            && self.texture == other.texture
+            && self.vertex_color == other.vertex_color
+    }
+}
@@ -1,2 +1,3 @@
+pub mod hit;
 pub mod ray;
 pub mod viewport;
\ No newline at end of file
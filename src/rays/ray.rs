@@ -5,6 +5,7 @@ use crate::errors::MainErr;
 
 use crate::basics::coord3::Coord3;
 use crate::basics::vec3::Vec3;
+use crate::rays::hit::HitRecord;
 
 #[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
 pub struct Ray {
@@ -27,21 +28,62 @@ impl Ray {
     pub fn get_direction(&self) -> &Vec3 {
         &self.direction
     }
+
+    /// 光线沿射出方向行进 `t` 后到达的位置
+    pub fn at(&self, t: f64) -> Coord3 {
+        self.origin + t * self.direction
+    }
 }
 
 pub trait RayIntersectOpaque {
-    /// 光线与不透明物体的交点
-    fn intersection(&self, ray: &Ray) -> Result<Option<Coord3>, Box<dyn Error>>;
+    /// 光线与不透明物体最近的交点记录
+    ///
+    /// 只返回光线行进时间 `t` 落在 `t_bound`（闭区间 `[t_min, t_max]`）内的交点，
+    /// 区间外的交点（包括位于光线后方的交点）视为不相交
+    fn intersection(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<Option<HitRecord<'_>>, Box<dyn Error>>;
+}
+
+pub trait RayIntersectTransparent {
+    /// 光线所在直线进入与离开实心物体时的交点记录 `(enter, exit)`
+    ///
+    /// 光线源点可位于物体内部，此时 `enter` 位于光线后方（行进时间为负）；
+    /// 不相交或物体整体位于光线后方时返回 `None`
+    fn enter_n_exit(&self, ray: &Ray) -> Result<Option<(HitRecord<'_>, HitRecord<'_>)>, Box<dyn Error>>;
+
+    /// 光线在 `t_bound` 区间内最先穿过物体表面的交点记录，光线源点可位于物体内部
+    fn surface_hit(&self, ray: &Ray, t_bound: (f64, f64)) -> Result<Option<HitRecord<'_>>, Box<dyn Error>> {
+        Ok(self
+            .enter_n_exit(ray)?
+            .and_then(|(enter, exit)| [enter, exit].into_iter().find(|hit| t_in_bound(hit.get_t(), t_bound))))
+    }
+}
+
+/// 将 `RayIntersectErr::InnerRayErr` 视为未相交，其余结果原样返回
+///
+/// 供场景容器在汇总多个物体的交点时使用
+pub fn skip_inner_ray(
+    result: Result<Option<HitRecord<'_>>, Box<dyn Error>>,
+) -> Result<Option<HitRecord<'_>>, Box<dyn Error>> {
+    match result {
+        Err(e) => match e.downcast_ref::<RayIntersectErr>() {
+            Some(RayIntersectErr::InnerRayErr) => Ok(None),
+            _ => Err(e),
+        },
+        res => res,
+    }
+}
+
+/// 检查光线行进时间 `t` 是否落在 `t_bound` 内
+pub fn t_in_bound(t: f64, t_bound: (f64, f64)) -> bool {
+    t_bound.0 <= t && t <= t_bound.1
 }
 
 #[derive(Debug)]
 pub enum RayIntersectErr {
-    /// 光线由不透明物体内部发出
+    /// 光线由不透明物体内部发出（需要穿透物体时改用 `RayIntersectTransparent`）
     InnerRayErr,
     /// 计算时出现 f64::NaN 无效值
     RayIntersectNaNErr(MainErr),
-    /// 二次方程的根（表示光线沿射出方向行进的时间）为负
-    NegativeRootErr,
 }
 
 impl Display for RayIntersectErr {
@@ -49,10 +91,6 @@ impl Display for RayIntersectErr {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::InnerRayErr => write!(f, "ray inside the object"),
-            Self::NegativeRootErr => write!(
-                f,
-                "quadratic equation has negative root"
-            ),
             Self::RayIntersectNaNErr(e) => write!(f, "{}", e),
         }
     }
@@ -64,4 +102,7 @@ impl RayIntersectErr {
     pub fn handle(&self) {
         eprintln!("[Ray Intersect Error] {}", self);
     }
-}
\ No newline at end of file
+}
+
+/// 光线沿射出方向的全部区间 `[0, +∞]`
+pub const FORWARD_T_BOUND: (f64, f64) = (0.0, f64::INFINITY);
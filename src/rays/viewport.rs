@@ -1,3 +1,440 @@
-pub struct Veiwport {
-    
-}
\ No newline at end of file
+use std::error::Error;
+use std::f64::consts::PI;
+use std::fmt::Display;
+
+use crate::basics::coord3::Coord3;
+use crate::basics::image::Img;
+use crate::basics::random;
+use crate::basics::vec3::{Vec3, ZERO_VEC3};
+use crate::errors::{MainErr, nan};
+use crate::objects::alignedbox::AlignedBox;
+use crate::rays::ray::Ray;
+
+/// 相机模型
+///
+/// 相机位于 `look_from`，朝向 `look_at`，`vup` 决定画面的“上”方向，
+/// 画面纵横比取自创建时传入的 `Img`
+///
+/// 默认为针孔相机；通过 `Viewport::set_lens` 设置光圈半径与对焦距离后成为薄透镜相机，
+/// 通过 `Viewport::set_projection` 可切换为正交或全景投影
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct Viewport {
+    /// 投影方式
+    projection: Projection,
+    /// 相机所在位置（即透镜中心）
+    origin: Coord3,
+    /// 相机坐标系：指向画面右侧的单位向量
+    u: Vec3,
+    /// 相机坐标系：指向画面上方的单位向量
+    v: Vec3,
+    /// 相机坐标系：指向相机后方的单位向量
+    w: Vec3,
+    /// 距相机单位距离处成像平面的宽度
+    unit_plane_w: f64,
+    /// 距相机单位距离处成像平面的高度
+    unit_plane_h: f64,
+    /// 透镜（光圈）半径，为 `0.0` 时退化为针孔相机
+    lens_radius: f64,
+    /// 对焦距离，成像平面即位于此处
+    focus_dist: f64,
+    /// 成像平面左上角的坐标
+    upper_left: Coord3,
+    /// 成像平面从左到右的跨度
+    horizontal: Vec3,
+    /// 成像平面从上到下的跨度
+    vertical: Vec3,
+    /// 图像宽度（像素）
+    img_w: usize,
+    /// 图像高度（像素）
+    img_h: usize,
+}
+
+impl Viewport {
+    /// 创建一个针孔相机
+    ///
+    /// `vfov` 为竖直方向视场角（角度制），取值需在 `(0, 180)` 内
+    ///
+    /// 当 `vfov` 无效、`look_from` 与 `look_at` 重合或 `vup` 与视线平行时，
+    /// 返回 `ViewportErr::InvalidParamErr`
+    pub fn new_from(
+        look_from: Coord3,
+        look_at: Coord3,
+        vup: Vec3,
+        vfov: f64,
+        img: &Img,
+    ) -> Result<Self, Box<dyn Error>> {
+        let vfov = nan::check::<MainErr>(vfov, "Viewport::new_from")?;
+        if vfov <= 0.0 || vfov >= 180.0 {
+            return Err(Box::new(ViewportErr::InvalidParamErr));
+        }
+
+        let back: Vec3 = look_from - look_at;
+        if back == ZERO_VEC3 {
+            return Err(Box::new(ViewportErr::InvalidParamErr));
+        }
+        let w: Vec3 = back.normalize();
+        let side: Vec3 = vup.cross(&w);
+        if side == ZERO_VEC3 {
+            return Err(Box::new(ViewportErr::InvalidParamErr));
+        }
+        let u: Vec3 = side.normalize();
+        let v: Vec3 = w.cross(&u);
+
+        let aspect_ratio: f64 = img.get_w() as f64 / img.get_h() as f64;
+        let plane_h: f64 = 2.0 * (vfov.to_radians() / 2.0).tan();
+        let plane_w: f64 = plane_h * aspect_ratio;
+
+        let mut viewport = Self {
+            projection: Projection::Perspective,
+            origin: look_from,
+            u,
+            v,
+            w,
+            unit_plane_w: plane_w,
+            unit_plane_h: plane_h,
+            lens_radius: 0.0,
+            focus_dist: 1.0,
+            upper_left: look_from,
+            horizontal: ZERO_VEC3,
+            vertical: ZERO_VEC3,
+            img_w: img.get_w(),
+            img_h: img.get_h(),
+        };
+        viewport.update_plane();
+        Ok(viewport)
+    }
+
+    /// 创建一个恰好能完整拍到 `bound` 的针孔相机
+    ///
+    /// 相机对准 `bound` 的中心，位于中心沿 `direction` 方向的一侧，
+    /// 距离取能让 `bound` 的外接球同时落在竖直与水平视场内的最小值
+    ///
+    /// 参数无效时的返回同 `Viewport::new_from`
+    pub fn new_framing(
+        bound: &AlignedBox,
+        direction: Vec3,
+        vup: Vec3,
+        vfov: f64,
+        img: &Img,
+    ) -> Result<Self, Box<dyn Error>> {
+        let vfov = nan::check::<MainErr>(vfov, "Viewport::new_framing")?;
+        if vfov <= 0.0 || vfov >= 180.0 || direction == ZERO_VEC3 {
+            return Err(Box::new(ViewportErr::InvalidParamErr));
+        }
+
+        let aspect_ratio: f64 = img.get_w() as f64 / img.get_h() as f64;
+        let half_vfov: f64 = vfov.to_radians() / 2.0;
+        let half_hfov: f64 = (half_vfov.tan() * aspect_ratio).atan();
+        let half_fov: f64 = half_vfov.min(half_hfov);
+
+        let center: Coord3 = bound.centroid();
+        let radius: f64 = bound.get_min().distance_to(&bound.get_max()) / 2.0;
+        let distance: f64 = radius / half_fov.sin();
+
+        Self::new_from(
+            center + distance * direction.normalize(),
+            center,
+            vup,
+            vfov,
+            img,
+        )
+    }
+
+    /// 设置薄透镜参数：光圈半径 `aperture` 与对焦距离 `focus_dist`
+    ///
+    /// 光线源点会在透镜圆盘上随机抖动，并汇聚于对焦平面，从而产生景深模糊；
+    /// `aperture` 为 `0.0` 时退化为针孔相机
+    ///
+    /// 当 `aperture` 为负或 `focus_dist` 不为正时，返回 `ViewportErr::InvalidParamErr`
+    pub fn set_lens(&mut self, aperture: f64, focus_dist: f64) -> Result<&mut Self, Box<dyn Error>> {
+        let aperture = nan::check::<MainErr>(aperture, "Viewport::set_lens")?;
+        let focus_dist = nan::check::<MainErr>(focus_dist, "Viewport::set_lens")?;
+        if aperture < 0.0 || focus_dist <= 0.0 {
+            return Err(Box::new(ViewportErr::InvalidParamErr));
+        }
+        self.lens_radius = aperture;
+        self.focus_dist = focus_dist;
+        self.update_plane();
+        Ok(self)
+    }
+
+    /// 设置投影方式
+    ///
+    /// 当正交投影的成像平面高度不为正时，返回 `ViewportErr::InvalidParamErr`
+    pub fn set_projection(&mut self, projection: Projection) -> Result<&mut Self, Box<dyn Error>> {
+        if let Projection::Orthographic(height) = projection {
+            let height = nan::check::<MainErr>(height, "Viewport::set_projection")?;
+            if height <= 0.0 {
+                return Err(Box::new(ViewportErr::InvalidParamErr));
+            }
+        }
+        self.projection = projection;
+        Ok(self)
+    }
+
+    pub fn get_projection(&self) -> Projection {
+        self.projection
+    }
+
+    pub fn get_aperture(&self) -> f64 {
+        self.lens_radius
+    }
+
+    pub fn get_focus_dist(&self) -> f64 {
+        self.focus_dist
+    }
+
+    /// 私有方法，根据对焦距离重新计算成像平面
+    fn update_plane(&mut self) {
+        self.horizontal = (self.unit_plane_w * self.focus_dist) * self.u;
+        self.vertical = (self.unit_plane_h * self.focus_dist) * self.v;
+        self.upper_left = self.origin
+            + (0.5 * self.vertical - 0.5 * self.horizontal - self.focus_dist * self.w);
+    }
+
+    pub fn get_origin(&self) -> &Coord3 {
+        &self.origin
+    }
+
+    pub fn get_w(&self) -> usize {
+        self.img_w
+    }
+
+    pub fn get_h(&self) -> usize {
+        self.img_h
+    }
+
+    /// 返回穿过成像平面上 `(x, y)` 位置的光线
+    ///
+    /// `x` 取值 `[0, img_w]`，从左向右；`y` 取值 `[0, img_h]`，从上向下，
+    /// 均以像素为单位，可取小数以表示像素内的采样点
+    ///
+    /// 透视投影下若设置了光圈，光线源点在透镜圆盘上随机选取
+    pub fn get_ray(&self, x: f64, y: f64) -> Ray {
+        let s: f64 = x / self.img_w as f64;
+        let t: f64 = y / self.img_h as f64;
+
+        match self.projection {
+            Projection::Perspective => self.perspective_ray(s, t),
+            Projection::Orthographic(height) => {
+                let width: f64 = height * self.unit_plane_w / self.unit_plane_h;
+                let offset: Vec3 = ((s - 0.5) * width) * self.u + ((0.5 - t) * height) * self.v;
+                Ray::new_from(self.origin + offset, -self.w)
+            }
+            Projection::Equirectangular => {
+                // 水平方向覆盖经度 [-π, π]，竖直方向覆盖纬度 [π/2, -π/2]，画面中心为视线方向
+                let longitude: f64 = (s - 0.5) * 2.0 * PI;
+                let latitude: f64 = (0.5 - t) * PI;
+                let direction: Vec3 = (latitude.cos() * longitude.sin()) * self.u
+                    + latitude.sin() * self.v
+                    - (latitude.cos() * longitude.cos()) * self.w;
+                Ray::new_from(self.origin, direction)
+            }
+        }
+    }
+
+    /// 私有方法，透视（针孔或薄透镜）投影下的光线
+    fn perspective_ray(&self, s: f64, t: f64) -> Ray {
+        let target: Coord3 = self.upper_left + (s * self.horizontal - t * self.vertical);
+
+        let lens_origin: Coord3 = if self.lens_radius > 0.0 {
+            let (dx, dy) = random::random_in_unit_disk();
+            self.origin + self.lens_radius * (dx * self.u + dy * self.v)
+        } else {
+            self.origin
+        };
+        Ray::new_from(lens_origin, target - lens_origin)
+    }
+
+    /// 返回穿过第 `line` 行、第 `col` 列（均为 0 索引）像素中心的光线
+    pub fn pixel_ray(&self, line: usize, col: usize) -> Ray {
+        self.get_ray(col as f64 + 0.5, line as f64 + 0.5)
+    }
+
+    /// 按 `Img::append` 的顺序（逐行，从左到右）依次返回每个像素中心的光线
+    pub fn rays(&self) -> ViewportRayIter<'_> {
+        ViewportRayIter {
+            index: 0,
+            viewport: self,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub enum Projection {
+    /// 透视投影（针孔或薄透镜）
+    Perspective,
+    /// 正交投影，参数为成像平面的高度（世界坐标单位），宽度按 `Img` 纵横比确定
+    Orthographic(f64),
+    /// 等距柱状（经纬度）投影，覆盖以相机为中心的整个球面
+    Equirectangular,
+}
+
+/// 立方体贴图的六个面，朝向沿世界坐标轴
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub enum CubeFace {
+    PosX,
+    NegX,
+    PosY,
+    NegY,
+    PosZ,
+    NegZ,
+}
+
+pub const CUBE_FACES: [CubeFace; 6] = [
+    CubeFace::PosX,
+    CubeFace::NegX,
+    CubeFace::PosY,
+    CubeFace::NegY,
+    CubeFace::PosZ,
+    CubeFace::NegZ,
+];
+
+/// 以 `center` 为中心的立方体贴图相机，每个面为 `size * size` 的正方形图像
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct CubeMap {
+    center: Coord3,
+    size: usize,
+}
+
+impl CubeMap {
+    /// 创建立方体贴图相机，每个面的尺寸取自 `img`
+    ///
+    /// 当 `img` 不是正方形时返回 `ViewportErr::InvalidParamErr`
+    pub fn new_from(center: Coord3, img: &Img) -> Result<Self, Box<dyn Error>> {
+        if img.get_w() != img.get_h() {
+            return Err(Box::new(ViewportErr::InvalidParamErr));
+        }
+        Ok(Self {
+            center,
+            size: img.get_w(),
+        })
+    }
+
+    pub fn get_center(&self) -> &Coord3 {
+        &self.center
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.size
+    }
+
+    /// 返回穿过 `face` 面上 `(x, y)` 位置的光线，坐标约定同 `Viewport::get_ray`
+    ///
+    /// 各面朝向遵循 OpenGL 立方体贴图约定
+    pub fn face_ray(&self, face: CubeFace, x: f64, y: f64) -> Ray {
+        let s: f64 = 2.0 * x / self.size as f64 - 1.0;
+        let t: f64 = 2.0 * y / self.size as f64 - 1.0;
+        let direction: Vec3 = match face {
+            CubeFace::PosX => Vec3::new_from(1.0, -t, -s),
+            CubeFace::NegX => Vec3::new_from(-1.0, -t, s),
+            CubeFace::PosY => Vec3::new_from(s, 1.0, t),
+            CubeFace::NegY => Vec3::new_from(s, -1.0, -t),
+            CubeFace::PosZ => Vec3::new_from(s, -t, 1.0),
+            CubeFace::NegZ => Vec3::new_from(-s, -t, -1.0),
+        };
+        Ray::new_from(self.center, direction)
+    }
+
+    /// 分别渲染六个面，按 `CUBE_FACES` 的顺序返回六个 `Img`
+    ///
+    /// `shade` 为每条像素中心光线返回浮点 RGB，其错误会原样返回
+    pub fn render_faces<F>(&self, mut shade: F) -> Result<Vec<Img>, Box<dyn Error>>
+    where
+        F: FnMut(&Ray) -> Result<(f64, f64, f64), Box<dyn Error>>,
+    {
+        let mut faces: Vec<Img> = Vec::with_capacity(CUBE_FACES.len());
+        for face in CUBE_FACES {
+            let mut img = Img::new_from(self.size, self.size)?;
+            for line in 0..self.size {
+                for col in 0..self.size {
+                    let ray = self.face_ray(face, col as f64 + 0.5, line as f64 + 0.5);
+                    let (r, g, b) = shade(&ray)?;
+                    img.append(r, g, b)?;
+                }
+            }
+            faces.push(img);
+        }
+        Ok(faces)
+    }
+
+    /// 渲染为一张横向十字展开图（宽 `4 * size`，高 `3 * size`）
+    ///
+    /// ```text
+    ///       +Y
+    /// -X    +Z    +X    -Z
+    ///       -Y
+    /// ```
+    ///
+    /// 十字以外的区域填充为黑色
+    pub fn render_cross<F>(&self, mut shade: F) -> Result<Img, Box<dyn Error>>
+    where
+        F: FnMut(&Ray) -> Result<(f64, f64, f64), Box<dyn Error>>,
+    {
+        let mut img = Img::new_from(4 * self.size, 3 * self.size)?;
+        for line in 0..3 * self.size {
+            for col in 0..4 * self.size {
+                let face: Option<CubeFace> = match (line / self.size, col / self.size) {
+                    (0, 1) => Some(CubeFace::PosY),
+                    (1, 0) => Some(CubeFace::NegX),
+                    (1, 1) => Some(CubeFace::PosZ),
+                    (1, 2) => Some(CubeFace::PosX),
+                    (1, 3) => Some(CubeFace::NegZ),
+                    (2, 1) => Some(CubeFace::NegY),
+                    _ => None,
+                };
+                match face {
+                    Some(face) => {
+                        let x: f64 = (col % self.size) as f64 + 0.5;
+                        let y: f64 = (line % self.size) as f64 + 0.5;
+                        let (r, g, b) = shade(&self.face_ray(face, x, y))?;
+                        img.append(r, g, b)?;
+                    }
+                    None => img.append(0.0, 0.0, 0.0)?,
+                }
+            }
+        }
+        Ok(img)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ViewportRayIter<'a> {
+    index: usize,
+    viewport: &'a Viewport,
+}
+
+impl Iterator for ViewportRayIter<'_> {
+    type Item = Ray;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.viewport.get_w() * self.viewport.get_h() {
+            return None;
+        }
+        let line: usize = self.index / self.viewport.get_w();
+        let col: usize = self.index % self.viewport.get_w();
+        self.index += 1;
+        Some(self.viewport.pixel_ray(line, col))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ViewportErr {
+    /// 输入了无效的相机参数
+    InvalidParamErr,
+}
+
+impl Display for ViewportErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidParamErr => write!(f, "invalid viewport construction"),
+        }
+    }
+}
+
+impl Error for ViewportErr {}
+
+impl ViewportErr {
+    pub fn handle(&self) {
+        eprintln!("[Viewport Error] {}", self);
+    }
+}
@@ -0,0 +1,501 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::basics::color::{BLACK, Color, WHITE};
+use crate::basics::random;
+use crate::basics::vec3::Vec3;
+use crate::errors::{MainErr, nan};
+use crate::basics::coord3::Coord3;
+use crate::objects::material::{Dielectric, ScatterRecord};
+use crate::objects::medium::ConstantMedium;
+use crate::objects::texture::{OpaqueMaterial, OpaqueTexture};
+use crate::objects::world::World;
+use crate::rays::hit::HitRecord;
+use crate::rays::ray::Ray;
+
+/// 计算沿光线方向到达的辐射度（颜色）
+pub trait Integrator {
+    /// 返回沿 `ray` 反方向进入观察者的辐射度
+    fn radiance(&self, ray: &Ray, world: &World) -> Result<Color, Box<dyn Error>>;
+}
+
+/// 多重重要性采样（MIS）中为各采样策略分配权重的启发式
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub enum MisHeuristic {
+    /// 平衡启发式：`p / (p + q)`
+    Balance,
+    /// 指数为 2 的幂启发式：`p² / (p² + q²)`
+    Power,
+}
+
+impl MisHeuristic {
+    /// 以概率密度 `pdf` 采得的样本，在另一策略的概率密度为 `other_pdf` 时的权重
+    pub fn weight(&self, pdf: f64, other_pdf: f64) -> f64 {
+        let (p, q) = match self {
+            Self::Balance => (pdf, other_pdf),
+            Self::Power => (pdf * pdf, other_pdf * other_pdf),
+        };
+        if p + q > 0.0 { p / (p + q) } else { 0.0 }
+    }
+}
+
+/// 路径追踪积分器
+///
+/// 光线在物体表面按材质的散射模型不断弹射，直至离开场景（取背景色）、被吸收、
+/// 达到最大弹射次数或被俄罗斯轮盘赌终止；途经自发光表面时累加其发出的辐射度；
+/// 在参与介质（`ConstantMedium`）内发生碰撞时按介质的相函数散射
+///
+/// 启用光源采样时，每次弹射还会向场景的 `Light` 光源与一个随机选取的发光体各发出阴影光线
+/// （next-event estimation），发光体的采样结果与散射光线击中发光体的结果按 `MisHeuristic` 加权合并
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct PathIntegrator {
+    /// 最大弹射次数
+    max_depth: usize,
+    /// 从第几次弹射开始启用俄罗斯轮盘赌
+    rr_depth: usize,
+    /// 光线离开场景时得到的背景辐射度
+    background: Color,
+    /// 光源采样的权重启发式，为 `None` 时不做光源采样
+    mis: Option<MisHeuristic>,
+}
+
+impl PathIntegrator {
+    /// 创建路径追踪积分器，背景默认为白色，默认以幂启发式做光源采样
+    ///
+    /// `max_depth` 为 `0` 时返回 `IntegratorErr::InvalidDepthErr`
+    pub fn new_from(max_depth: usize, rr_depth: usize) -> Result<Self, Box<dyn Error>> {
+        if max_depth == 0 {
+            return Err(Box::new(IntegratorErr::InvalidDepthErr));
+        }
+        Ok(Self {
+            max_depth,
+            rr_depth,
+            background: WHITE,
+            mis: Some(MisHeuristic::Power),
+        })
+    }
+
+    pub fn get_max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    pub fn get_rr_depth(&self) -> usize {
+        self.rr_depth
+    }
+
+    pub fn get_background(&self) -> Color {
+        self.background
+    }
+
+    pub fn set_background(&mut self, background: Color) -> &mut Self {
+        self.background = background;
+        self
+    }
+
+    pub fn get_mis(&self) -> Option<MisHeuristic> {
+        self.mis
+    }
+
+    /// 设置光源采样的权重启发式，`None` 表示关闭光源采样（纯路径追踪）
+    pub fn set_mis(&mut self, mis: Option<MisHeuristic>) -> &mut Self {
+        self.mis = mis;
+        self
+    }
+
+    /// 私有方法，散射光线 `ray` 击中自发光表面 `hit` 时其发光的 MIS 权重
+    ///
+    /// `specular` 与 `scatter_pdf` 为产生 `ray` 的那次散射是否为镜面散射及其概率密度
+    fn emission_weight(
+        &self,
+        ray: &Ray,
+        hit: &HitRecord<'_>,
+        world: &World,
+        specular: bool,
+        scatter_pdf: f64,
+    ) -> f64 {
+        let heuristic: MisHeuristic = match self.mis {
+            Some(heuristic) if !specular => heuristic,
+            _ => return 1.0,
+        };
+        let emitter = match hit.get_emitter() {
+            Some(emitter) => emitter,
+            None => return 1.0,
+        };
+        let light_pdf: f64 =
+            world.emitter_select_pdf(emitter) * emitter.emitter_pdf(ray.get_origin(), ray.get_direction());
+        heuristic.weight(scatter_pdf, light_pdf)
+    }
+
+    /// 私有方法，散射点 `point` 处经光源采样得到的直接光照
+    ///
+    /// `scatter` 给出向某一单位向量方向散射时 BSDF（或相函数）与余弦项之积，以及散射采样得到该方向的概率密度
+    fn sample_direct(
+        &self,
+        point: &Coord3,
+        world: &World,
+        heuristic: MisHeuristic,
+        scatter: &dyn Fn(&Vec3) -> (Color, f64),
+    ) -> Result<Color, Box<dyn Error>> {
+        let mut direct: Color = Color::new();
+
+        // 理想光源无法被散射光线击中，其贡献全部来自光源采样
+        for light in world.get_lights() {
+            let sample = match light.illuminate(point) {
+                Some(sample) => sample,
+                None => continue,
+            };
+            let (bsdf, _) = scatter(sample.get_direction());
+            if bsdf.is_black() || is_occluded(world, point, sample.get_direction(), sample.get_distance())? {
+                continue;
+            }
+            direct += bsdf * sample.get_radiance();
+        }
+
+        let count: usize = world.emitter_count();
+        if count == 0 {
+            return Ok(direct);
+        }
+        let idx: usize = ((random::random_f64() * count as f64) as usize).min(count - 1);
+        let sample = match world.get_emitter(idx).and_then(|emitter| emitter.sample_emitter(point)) {
+            Some(sample) => sample,
+            None => return Ok(direct),
+        };
+        let to_light: Vec3 = sample.get_point() - point;
+        let direction: Vec3 = to_light.normalize();
+        let (bsdf, scatter_pdf) = scatter(&direction);
+        if bsdf.is_black() || is_occluded(world, point, &direction, to_light.magnitude())? {
+            return Ok(direct);
+        }
+        let light_pdf: f64 = sample.get_pdf() / count as f64;
+        let weight: f64 = heuristic.weight(light_pdf, scatter_pdf);
+        direct += bsdf * sample.get_radiance() * (weight / light_pdf);
+
+        Ok(direct)
+    }
+}
+
+impl Integrator for PathIntegrator {
+    fn radiance(&self, ray: &Ray, world: &World) -> Result<Color, Box<dyn Error>> {
+        let mut radiance: Color = Color::new();
+        let mut throughput: Color = WHITE;
+        let mut ray: Ray = *ray;
+        // 产生当前光线的那次散射是否为镜面散射及其概率密度，摄像机光线视同镜面散射
+        let mut specular: bool = true;
+        let mut scatter_pdf: f64 = 0.0;
+        // 没有材质的物体按白色漫反射处理
+        let default_texture: OpaqueTexture = OpaqueTexture::default();
+
+        for depth in 0..self.max_depth {
+            let hit = match world.closest_hit(&ray, (SELF_HIT_EPSILON, f64::INFINITY))? {
+                Some(hit) => hit,
+                None => {
+                    radiance += throughput * self.background;
+                    break;
+                }
+            };
+
+            let scattered: ScatterRecord = match hit.get_medium() {
+                // 参与介质内的碰撞点：按相函数散射
+                Some(medium) => {
+                    let (phase, albedo) = (medium.get_phase(), medium.albedo());
+                    let direction: &Vec3 = ray.get_direction();
+                    if let Some(heuristic) = self.mis {
+                        let scatter = |wi: &Vec3| -> (Color, f64) {
+                            let pdf: f64 = phase.eval(direction, wi);
+                            (pdf * albedo, pdf)
+                        };
+                        radiance += throughput * self.sample_direct(hit.get_point(), world, heuristic, &scatter)?;
+                    }
+                    let scattered: Vec3 = phase.sample(direction);
+                    scatter_pdf = phase.eval(direction, &scattered);
+                    ScatterRecord::new_from(albedo, Ray::new_from(*hit.get_point(), scattered), false)
+                }
+                None => {
+                    let texture: &OpaqueTexture = hit.get_texture().unwrap_or(&default_texture);
+                    if texture.is_emissive() {
+                        let weight: f64 = self.emission_weight(&ray, &hit, world, specular, scatter_pdf);
+                        radiance += throughput * texture.emitted() * weight;
+                    }
+
+                    let scattered: ScatterRecord = match texture.scatter(&ray, &hit) {
+                        Some(scattered) => scattered,
+                        None => break,
+                    };
+                    if let Some(heuristic) = self.mis {
+                        let scatter = |wi: &Vec3| -> (Color, f64) {
+                            (texture.eval(&ray, &hit, wi), texture.pdf(&ray, &hit, wi))
+                        };
+                        radiance += throughput * self.sample_direct(hit.get_point(), world, heuristic, &scatter)?;
+                    }
+                    scatter_pdf = if scattered.is_specular() {
+                        0.0
+                    } else {
+                        texture.pdf(&ray, &hit, scattered.get_ray().get_direction())
+                    };
+                    scattered
+                }
+            };
+
+            throughput = throughput * scattered.get_attenuation();
+            specular = scattered.is_specular();
+
+            // 俄罗斯轮盘赌：以当前通量为存活概率，存活的路径按概率放大以保持无偏
+            if depth + 1 >= self.rr_depth {
+                let survival: f64 = throughput.max_component().min(0.95);
+                if survival <= 0.0 || random::random_f64() >= survival {
+                    break;
+                }
+                throughput = throughput / survival;
+            }
+
+            ray = *scattered.get_ray();
+        }
+
+        Ok(radiance)
+    }
+}
+
+/// Whitted 风格的直接光照积分器
+///
+/// 只计算场景中 `Light` 光源的直接照明（Lambert 漫反射加 Blinn-Phong 高光，阴影由阴影光线判定），
+/// 再对镜面反射、玻璃折射及半透明表面递归追踪，不计算漫反射表面之间的间接光照；
+/// 参与介质只计算一次散射的直接照明
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy)]
+pub struct WhittedIntegrator {
+    /// 最大递归深度
+    max_depth: usize,
+    /// 环境光，按表面颜色均匀叠加
+    ambient: Color,
+    /// Blinn-Phong 高光指数，越大高光越集中
+    shininess: f64,
+    /// 光线离开场景时得到的背景辐射度
+    background: Color,
+}
+
+impl WhittedIntegrator {
+    /// 创建 Whitted 积分器，环境光与背景默认为黑色，高光指数默认为 `32`
+    ///
+    /// `max_depth` 为 `0` 时返回 `IntegratorErr::InvalidDepthErr`
+    pub fn new_from(max_depth: usize) -> Result<Self, Box<dyn Error>> {
+        if max_depth == 0 {
+            return Err(Box::new(IntegratorErr::InvalidDepthErr));
+        }
+        Ok(Self {
+            max_depth,
+            ambient: BLACK,
+            shininess: 32.0,
+            background: BLACK,
+        })
+    }
+
+    pub fn get_max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    pub fn get_ambient(&self) -> Color {
+        self.ambient
+    }
+
+    pub fn get_shininess(&self) -> f64 {
+        self.shininess
+    }
+
+    pub fn get_background(&self) -> Color {
+        self.background
+    }
+
+    pub fn set_ambient(&mut self, ambient: Color) -> &mut Self {
+        self.ambient = ambient;
+        self
+    }
+
+    /// `shininess` 为 `f64::NAN` 时返回 `MainErr`，不为正时返回 `IntegratorErr::InvalidShininessErr`
+    pub fn set_shininess(&mut self, shininess: f64) -> Result<&mut Self, Box<dyn Error>> {
+        let shininess: f64 = nan::check::<MainErr>(shininess, "WhittedIntegrator::set_shininess")?;
+        if shininess <= 0.0 {
+            return Err(Box::new(IntegratorErr::InvalidShininessErr));
+        }
+        self.shininess = shininess;
+        Ok(self)
+    }
+
+    pub fn set_background(&mut self, background: Color) -> &mut Self {
+        self.background = background;
+        self
+    }
+
+    /// 私有方法，追踪第 `depth` 层光线
+    fn trace(&self, ray: &Ray, world: &World, depth: usize) -> Result<Color, Box<dyn Error>> {
+        if depth >= self.max_depth {
+            return Ok(BLACK);
+        }
+        let hit = match world.closest_hit(ray, (SELF_HIT_EPSILON, f64::INFINITY))? {
+            Some(hit) => hit,
+            None => return Ok(self.background),
+        };
+        if let Some(medium) = hit.get_medium() {
+            return self.medium_direct(ray, &hit, medium, world);
+        }
+
+        // 没有材质的物体按白色、不反射的漫反射表面处理
+        let (albedo, alpha, reflectance, material) = match hit.get_texture() {
+            Some(texture) => (
+                texture.color_at(&hit),
+                texture.get_alpha(),
+                texture.get_reflectance(),
+                texture.get_material(),
+            ),
+            None => (WHITE, 1.0, 0.0, OpaqueMaterial::Null),
+        };
+        let point = *hit.get_point();
+        let direction: &Vec3 = ray.get_direction();
+        let normal: &Vec3 = hit.get_shading_normal();
+        let reflected = Ray::new_from(point, direction.reflect(normal));
+
+        let surface: Color = match material {
+            OpaqueMaterial::Metal(_) => albedo * self.trace(&reflected, world, depth + 1)?,
+            OpaqueMaterial::Glass(ior) => {
+                let ior: f64 = ior.get_value();
+                // 按 Fresnel 系数同时追踪反射与折射，而非随机选择其一
+                let eta_ratio: f64 = if hit.is_front_face() { 1.0 / ior } else { ior };
+                let cos_theta: f64 = (-(direction * normal)).min(1.0);
+                match direction.refract(normal, eta_ratio) {
+                    Some(refracted) => {
+                        let fresnel: f64 = Dielectric::schlick(cos_theta, eta_ratio);
+                        let refracted = Ray::new_from(point, refracted);
+                        albedo
+                            * (fresnel * self.trace(&reflected, world, depth + 1)?
+                                + (1.0 - fresnel) * self.trace(&refracted, world, depth + 1)?)
+                    }
+                    None => albedo * self.trace(&reflected, world, depth + 1)?,
+                }
+            }
+            OpaqueMaterial::Plastic
+            | OpaqueMaterial::Fabric
+            | OpaqueMaterial::Rubber
+            | OpaqueMaterial::Wood
+            | OpaqueMaterial::Null => {
+                let mut color: Color = self.direct(ray, &hit, world, albedo, reflectance)?;
+                if reflectance > 0.0 {
+                    color += reflectance * albedo * self.trace(&reflected, world, depth + 1)?;
+                }
+                color
+            }
+        };
+
+        let surface: Color = match hit.get_texture() {
+            Some(texture) => surface + texture.emitted(),
+            None => surface,
+        };
+
+        // 半透明表面：`1 - alpha` 的光线不受影响地穿过
+        if alpha < 1.0 {
+            let passed = Ray::new_from(point, *direction);
+            return Ok(alpha * surface + (1.0 - alpha) * self.trace(&passed, world, depth + 1)?);
+        }
+        Ok(surface)
+    }
+
+    /// 私有方法，交点 `hit` 处来自全部光源的直接照明与环境光
+    ///
+    /// 漫反射与高光按 `reflectance` 分配权重
+    fn direct(
+        &self,
+        ray: &Ray,
+        hit: &HitRecord<'_>,
+        world: &World,
+        albedo: Color,
+        reflectance: f64,
+    ) -> Result<Color, Box<dyn Error>> {
+        let normal: &Vec3 = hit.get_shading_normal();
+        let mut color: Color = self.ambient * albedo;
+
+        for light in world.get_lights() {
+            let sample = match light.illuminate(hit.get_point()) {
+                Some(sample) => sample,
+                None => continue,
+            };
+            let to_light: &Vec3 = sample.get_direction();
+            let cos_theta: f64 = to_light * normal;
+            if cos_theta <= 0.0 {
+                continue;
+            }
+            if is_occluded(world, hit.get_point(), to_light, sample.get_distance())? {
+                continue;
+            }
+
+            let half: Vec3 = (to_light - ray.get_direction()).normalize();
+            let specular: f64 = (half * normal).max(0.0).powf(self.shininess);
+            let diffuse: Color = (1.0 - reflectance) * cos_theta * albedo;
+            color += sample.get_radiance() * (diffuse + WHITE * (reflectance * specular));
+        }
+
+        Ok(color)
+    }
+
+    /// 私有方法，参与介质内的碰撞点 `hit` 处来自全部光源的直接照明与环境光
+    fn medium_direct(
+        &self,
+        ray: &Ray,
+        hit: &HitRecord<'_>,
+        medium: &ConstantMedium,
+        world: &World,
+    ) -> Result<Color, Box<dyn Error>> {
+        let albedo: Color = medium.albedo();
+        let mut color: Color = self.ambient * albedo;
+
+        for light in world.get_lights() {
+            let sample = match light.illuminate(hit.get_point()) {
+                Some(sample) => sample,
+                None => continue,
+            };
+            if is_occluded(world, hit.get_point(), sample.get_direction(), sample.get_distance())? {
+                continue;
+            }
+            let phase: f64 = medium.get_phase().eval(ray.get_direction(), sample.get_direction());
+            color += sample.get_radiance() * (phase * albedo);
+        }
+
+        Ok(color)
+    }
+}
+
+impl Integrator for WhittedIntegrator {
+    fn radiance(&self, ray: &Ray, world: &World) -> Result<Color, Box<dyn Error>> {
+        self.trace(ray, world, 0)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum IntegratorErr {
+    /// 最大弹射次数无效
+    InvalidDepthErr,
+    /// 高光指数无效
+    InvalidShininessErr,
+}
+
+impl Display for IntegratorErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidDepthErr => write!(f, "invalid max depth"),
+            Self::InvalidShininessErr => write!(f, "invalid shininess"),
+        }
+    }
+}
+
+impl Error for IntegratorErr {}
+
+impl IntegratorErr {
+    pub fn handle(&self) {
+        eprintln!("[Integrator Error] {}", self);
+    }
+}
+
+/// 私有函数，由 `point` 沿单位向量 `direction` 到距离 `distance` 处之间是否有物体遮挡
+fn is_occluded(world: &World, point: &Coord3, direction: &Vec3, distance: f64) -> Result<bool, Box<dyn Error>> {
+    let shadow_ray = Ray::new_from(*point, *direction);
+    world.any_hit(&shadow_ray, (SELF_HIT_EPSILON, distance - SELF_HIT_EPSILON))
+}
+
+/// 散射光线的最小行进时间，避免与出发表面自相交
+pub const SELF_HIT_EPSILON: f64 = 1e-4;
@@ -0,0 +1,129 @@
+pub mod integrator;
+pub mod sampler;
+
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::basics::color::Color;
+use crate::basics::image::Img;
+use crate::errors::{MainErr, nan};
+use crate::rays::ray::Ray;
+use crate::rays::viewport::Viewport;
+use sampler::Sampler;
+
+/// 逐像素多重采样的渲染器
+///
+/// 每个像素按 `Sampler` 给出的 `spp` 个采样点生成光线，
+/// 对各光线的颜色取平均后再写入 `Img`
+pub struct Renderer {
+    sampler: Box<dyn Sampler>,
+    /// 每像素采样数（samples per pixel）
+    spp: usize,
+    /// 写入 `Img` 前做伽马校正所用的 gamma 值，`1.0` 表示不校正
+    gamma: f64,
+}
+
+impl Renderer {
+    /// 创建渲染器
+    ///
+    /// `spp` 为 `0` 时返回 `RenderErr::InvalidSppErr`
+    pub fn new_from(sampler: Box<dyn Sampler>, spp: usize) -> Result<Self, Box<dyn Error>> {
+        if spp == 0 {
+            return Err(Box::new(RenderErr::InvalidSppErr));
+        }
+        Ok(Self {
+            sampler,
+            spp,
+            gamma: 1.0,
+        })
+    }
+
+    pub fn get_spp(&self) -> usize {
+        self.spp
+    }
+
+    /// 更换采样方式
+    pub fn set_sampler(&mut self, sampler: Box<dyn Sampler>) -> &mut Self {
+        self.sampler = sampler;
+        self
+    }
+
+    /// 重设每像素采样数
+    ///
+    /// `spp` 为 `0` 时返回 `RenderErr::InvalidSppErr`
+    pub fn set_spp(&mut self, spp: usize) -> Result<&mut Self, Box<dyn Error>> {
+        if spp == 0 {
+            return Err(Box::new(RenderErr::InvalidSppErr));
+        }
+        self.spp = spp;
+        Ok(self)
+    }
+
+    /// 设置写入 `Img` 前伽马校正所用的 gamma 值（如 `2.2`），`1.0` 表示不校正
+    ///
+    /// `gamma` 不为正时返回 `RenderErr::InvalidGammaErr`
+    pub fn set_gamma(&mut self, gamma: f64) -> Result<&mut Self, Box<dyn Error>> {
+        let gamma = nan::check::<MainErr>(gamma, "Renderer::set_gamma")?;
+        if gamma <= 0.0 {
+            return Err(Box::new(RenderErr::InvalidGammaErr));
+        }
+        self.gamma = gamma;
+        Ok(self)
+    }
+
+    /// 渲染 `viewport` 所见的画面，返回与 `viewport` 同尺寸的 `Img`
+    ///
+    /// `shade` 返回每条光线的线性颜色，其错误会原样返回；
+    /// 含 `f64::NAN` 或无穷大分量的采样按黑色计入；
+    /// 像素的平均颜色经伽马校正并截断到 `[0, 1]` 后写入 `Img`
+    pub fn render<F>(&mut self, viewport: &Viewport, mut shade: F) -> Result<Img, Box<dyn Error>>
+    where
+        F: FnMut(&Ray) -> Result<Color, Box<dyn Error>>,
+    {
+        let mut img = Img::new_from(viewport.get_w(), viewport.get_h())?;
+
+        for line in 0..viewport.get_h() {
+            for col in 0..viewport.get_w() {
+                let pixel: usize = line * viewport.get_w() + col;
+                let mut sum: Color = Color::new();
+                for index in 0..self.spp {
+                    let (dx, dy) = self.sampler.sample(pixel, index, self.spp);
+                    let ray: Ray = viewport.get_ray(col as f64 + dx, line as f64 + dy);
+                    let color: Color = shade(&ray)?;
+                    if color.is_finite() {
+                        sum += color;
+                    }
+                }
+                let average: Color = (sum / self.spp as f64).gamma_corrected(self.gamma).clamp();
+                img.append(average.r(), average.g(), average.b())?;
+            }
+        }
+
+        Ok(img)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum RenderErr {
+    /// 每像素采样数无效
+    InvalidSppErr,
+    /// 伽马校正参数无效
+    InvalidGammaErr,
+}
+
+impl Display for RenderErr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidSppErr => write!(f, "invalid samples per pixel"),
+            Self::InvalidGammaErr => write!(f, "invalid gamma value"),
+        }
+    }
+}
+
+impl Error for RenderErr {}
+
+impl RenderErr {
+    pub fn handle(&self) {
+        eprintln!("[Render Error] {}", self);
+    }
+}
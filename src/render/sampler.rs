@@ -0,0 +1,108 @@
+use crate::basics::random;
+
+/// 像素内采样点的生成方式
+pub trait Sampler {
+    /// 返回第 `pixel` 个像素（按 `Img::append` 的顺序计数）共 `spp` 个采样中
+    /// 第 `index` 个采样点在像素内的偏移 `(dx, dy)`，两者取值均为 `[0, 1)`
+    fn sample(&mut self, pixel: usize, index: usize, spp: usize) -> (f64, f64);
+}
+
+/// 均匀随机采样
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Default)]
+pub struct UniformSampler;
+
+impl Sampler for UniformSampler {
+    fn sample(&mut self, _pixel: usize, _index: usize, _spp: usize) -> (f64, f64) {
+        (random::random_f64(), random::random_f64())
+    }
+}
+
+/// 分层（抖动）采样
+///
+/// 将像素划分为 `rows * cols == spp` 个网格（`rows` 取不超过 `spp` 平方根的最大因数），
+/// 依次在每个网格内随机取一点，使每个像素的 `spp` 个采样恰好覆盖整个像素；
+/// `spp` 为质数时网格退化为一行
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Default)]
+pub struct StratifiedSampler;
+
+impl Sampler for StratifiedSampler {
+    fn sample(&mut self, _pixel: usize, index: usize, spp: usize) -> (f64, f64) {
+        let spp: usize = spp.max(1);
+        let rows: usize = (1..=spp.isqrt()).rev().find(|n| spp.is_multiple_of(*n)).unwrap_or(1);
+        let cols: usize = spp / rows;
+        let stratum: usize = index % spp;
+        let dx: f64 = ((stratum % cols) as f64 + random::random_f64()) / cols as f64;
+        let dy: f64 = ((stratum / cols) as f64 + random::random_f64()) / rows as f64;
+        (dx, dy)
+    }
+}
+
+/// Halton 低差异序列采样（底数 2 与 3）
+///
+/// 每个像素使用由像素序号决定的随机平移（Cranley-Patterson 旋转），避免相邻像素出现相同的图样
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Default)]
+pub struct HaltonSampler;
+
+impl Sampler for HaltonSampler {
+    fn sample(&mut self, pixel: usize, index: usize, _spp: usize) -> (f64, f64) {
+        let (shift_x, shift_y) = pixel_shift(pixel);
+        let dx: f64 = radical_inverse(index as u64 + 1, 2) + shift_x;
+        let dy: f64 = radical_inverse(index as u64 + 1, 3) + shift_y;
+        (dx.fract(), dy.fract())
+    }
+}
+
+/// Sobol 低差异序列采样（前两维）
+///
+/// 每个像素使用由像素序号决定的随机异或扰动（数字置乱），避免相邻像素出现相同的图样
+#[derive(Debug, PartialEq, PartialOrd, Clone, Copy, Default)]
+pub struct SobolSampler;
+
+impl Sampler for SobolSampler {
+    fn sample(&mut self, pixel: usize, index: usize, _spp: usize) -> (f64, f64) {
+        let scramble: u64 = random::hash_u64(pixel as u64);
+        let mut x: u32 = scramble as u32;
+        let mut y: u32 = (scramble >> 32) as u32;
+
+        // 第一维的方向数为 1 << (31 - bit)，第二维由本原多项式 x + 1 递推
+        let mut v_y: u32 = 1 << 31;
+        let mut i: usize = index;
+        let mut bit: u32 = 0;
+        while i != 0 {
+            if i & 1 == 1 {
+                x ^= 1 << (31 - bit);
+                y ^= v_y;
+            }
+            v_y ^= v_y >> 1;
+            i >>= 1;
+            bit += 1;
+        }
+
+        (x as f64 * SOBOL_SCALE, y as f64 * SOBOL_SCALE)
+    }
+}
+
+/// 私有函数，`value` 在底数 `base` 下的根式逆
+fn radical_inverse(mut value: u64, base: u64) -> f64 {
+    let inv_base: f64 = 1.0 / base as f64;
+    let mut factor: f64 = inv_base;
+    let mut result: f64 = 0.0;
+    while value > 0 {
+        result += (value % base) as f64 * factor;
+        value /= base;
+        factor *= inv_base;
+    }
+    result
+}
+
+/// 私有函数，由像素序号决定的 `[0, 1)` 平移量
+fn pixel_shift(pixel: usize) -> (f64, f64) {
+    let hash: u64 = random::hash_u64(pixel as u64);
+    (
+        (hash >> 40) as f64 / (1_u64 << 24) as f64,
+        ((hash >> 16) & 0xFF_FFFF) as f64 / (1_u64 << 24) as f64,
+    )
+}
+
+/// 将 32 位整数映射到 `[0, 1)` 的比例参数
+const SOBOL_SCALE: f64 = 1.0 / 4_294_967_296.0;